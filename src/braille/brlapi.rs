@@ -0,0 +1,370 @@
+//! BrlAPI client
+//!
+//! Talks to the brltty daemon over its local socket using the BrlAPI
+//! protocol. Every packet is a big-endian header (payload size, packet type)
+//! followed by the payload. After the version and authentication handshake
+//! the client claims a tty, writes text to the display and receives key
+//! codes when the user presses keys on the braille device.
+
+use crate::{Result, TdsrError};
+use log::{debug, info};
+use std::io::{ErrorKind, Read, Write};
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::os::unix::net::UnixStream;
+use std::path::Path;
+
+/// Protocol version spoken by this client
+pub const PROTOCOL_VERSION: u32 = 8;
+
+/// Directory brltty creates its local sockets in
+pub const SOCKET_DIR: &str = "/var/lib/BrlAPI";
+
+/// Default authentication key file
+pub const DEFAULT_KEY_FILE: &str = "/etc/brlapi.key";
+
+/// Largest packet payload accepted from the server
+const MAX_PACKET_SIZE: usize = 512;
+
+/// Packet types (single ASCII characters on the wire)
+pub mod packet {
+    pub const VERSION: u32 = b'v' as u32;
+    pub const AUTH: u32 = b'a' as u32;
+    pub const ACK: u32 = b'A' as u32;
+    pub const ERROR: u32 = b'e' as u32;
+    pub const EXCEPTION: u32 = b'E' as u32;
+    pub const GET_DISPLAY_SIZE: u32 = b's' as u32;
+    pub const ENTER_TTY_MODE: u32 = b't' as u32;
+    pub const LEAVE_TTY_MODE: u32 = b'L' as u32;
+    pub const KEY: u32 = b'k' as u32;
+    pub const WRITE: u32 = b'w' as u32;
+}
+
+/// Authentication methods offered by the server
+pub mod auth {
+    pub const NONE: u32 = b'N' as u32;
+    pub const KEY: u32 = b'K' as u32;
+}
+
+/// Flags describing which fields a write packet carries
+pub mod write_flags {
+    pub const REGION: u32 = 0x02;
+    pub const TEXT: u32 = 0x04;
+    pub const CURSOR: u32 = 0x20;
+    pub const CHARSET: u32 = 0x40;
+}
+
+/// Key code layout (from brlapi_constants.h)
+pub mod key {
+    pub const TYPE_MASK: u64 = 0xE000_0000;
+    pub const TYPE_CMD: u64 = 0x2000_0000;
+    pub const CMD_BLK_MASK: u64 = 0x1FFF_0000;
+    pub const CMD_BLK_SHIFT: u64 = 16;
+    pub const CMD_ARG_MASK: u64 = 0x0000_FFFF;
+
+    /// Command block for cursor routing keys
+    pub const BLK_ROUTE: u64 = 1;
+
+    // brltty commands (block 0)
+    pub const CMD_LNUP: u64 = 1;
+    pub const CMD_LNDN: u64 = 2;
+    pub const CMD_WINUP: u64 = 3;
+    pub const CMD_WINDN: u64 = 4;
+    pub const CMD_PRDIFLN: u64 = 5;
+    pub const CMD_NXDIFLN: u64 = 6;
+    pub const CMD_TOP: u64 = 9;
+    pub const CMD_BOT: u64 = 10;
+    pub const CMD_TOP_LEFT: u64 = 11;
+    pub const CMD_BOT_LEFT: u64 = 12;
+    pub const CMD_CHRLT: u64 = 19;
+    pub const CMD_CHRRT: u64 = 20;
+    pub const CMD_HWINLT: u64 = 21;
+    pub const CMD_HWINRT: u64 = 22;
+    pub const CMD_FWINLT: u64 = 23;
+    pub const CMD_FWINRT: u64 = 24;
+    pub const CMD_LNBEG: u64 = 27;
+    pub const CMD_LNEND: u64 = 28;
+}
+
+/// A connection to the BrlAPI server
+pub struct BrlapiConnection {
+    /// Socket connected to brltty
+    stream: UnixStream,
+
+    /// Display dimensions in cells (columns, rows)
+    display_size: (u32, u32),
+
+    /// Bytes received but not yet parsed into packets
+    pending: Vec<u8>,
+}
+
+impl BrlapiConnection {
+    /// Connect to the server listening on `socket_path` and authenticate
+    ///
+    /// `key_file` is only read if the server asks for key authentication.
+    pub fn connect(socket_path: &Path, key_file: &Path) -> Result<Self> {
+        debug!("Connecting to BrlAPI at {:?}", socket_path);
+
+        let stream = UnixStream::connect(socket_path).map_err(|e| {
+            TdsrError::Braille(format!("Failed to connect to {:?}: {}", socket_path, e))
+        })?;
+
+        let mut conn = Self {
+            stream,
+            display_size: (0, 0),
+            pending: Vec::new(),
+        };
+
+        conn.handshake(key_file)?;
+        conn.display_size = conn.query_display_size()?;
+        info!(
+            "BrlAPI connected, display is {}x{}",
+            conn.display_size.0, conn.display_size.1
+        );
+
+        Ok(conn)
+    }
+
+    /// Exchange protocol versions and authenticate
+    fn handshake(&mut self, key_file: &Path) -> Result<()> {
+        let (ty, payload) = self.read_packet_blocking()?;
+        if ty != packet::VERSION || payload.len() < 4 {
+            return Err(TdsrError::Braille("Expected version packet".to_string()));
+        }
+        let server_version = read_u32(&payload, 0);
+        debug!("BrlAPI server protocol version {}", server_version);
+        if server_version != PROTOCOL_VERSION {
+            return Err(TdsrError::Braille(format!(
+                "Unsupported BrlAPI protocol version {} (need {})",
+                server_version, PROTOCOL_VERSION
+            )));
+        }
+
+        self.send_packet(packet::VERSION, &PROTOCOL_VERSION.to_be_bytes())?;
+
+        let (ty, payload) = self.read_packet_blocking()?;
+        match ty {
+            // No authentication required
+            packet::ACK => Ok(()),
+            packet::AUTH => {
+                let methods: Vec<u32> = payload
+                    .chunks_exact(4)
+                    .map(|chunk| read_u32(chunk, 0))
+                    .collect();
+                debug!("BrlAPI auth methods offered: {:?}", methods);
+
+                if methods.contains(&auth::NONE) {
+                    Ok(())
+                } else if methods.contains(&auth::KEY) {
+                    let key = std::fs::read(key_file).map_err(|e| {
+                        TdsrError::Braille(format!("Failed to read key file {:?}: {}", key_file, e))
+                    })?;
+                    let mut auth_payload = auth::KEY.to_be_bytes().to_vec();
+                    auth_payload.extend_from_slice(&key);
+                    self.send_packet(packet::AUTH, &auth_payload)?;
+                    self.expect_ack()
+                } else {
+                    Err(TdsrError::Braille(
+                        "No supported BrlAPI authentication method".to_string(),
+                    ))
+                }
+            }
+            other => Err(unexpected_packet(other, &payload)),
+        }
+    }
+
+    /// Ask the server for the display dimensions
+    fn query_display_size(&mut self) -> Result<(u32, u32)> {
+        self.send_packet(packet::GET_DISPLAY_SIZE, &[])?;
+        let (ty, payload) = self.read_packet_blocking()?;
+        if ty != packet::GET_DISPLAY_SIZE || payload.len() < 8 {
+            return Err(unexpected_packet(ty, &payload));
+        }
+        Ok((read_u32(&payload, 0), read_u32(&payload, 4)))
+    }
+
+    /// Display dimensions in cells (columns, rows)
+    pub fn display_size(&self) -> (u32, u32) {
+        self.display_size
+    }
+
+    /// Take control of the display for the given tty path
+    ///
+    /// Key presses are delivered as brltty command codes, so the
+    /// same code means the same thing on every braille device.
+    pub fn enter_tty_mode(&mut self, ttys: &[u32]) -> Result<()> {
+        let mut payload = (ttys.len() as u32).to_be_bytes().to_vec();
+        for tty in ttys {
+            payload.extend_from_slice(&tty.to_be_bytes());
+        }
+        // Empty driver name = receive driver-independent command codes
+        payload.push(0);
+
+        self.send_packet(packet::ENTER_TTY_MODE, &payload)?;
+        self.expect_ack()?;
+
+        // Keys arrive asynchronously from here on
+        self.stream.set_nonblocking(true)?;
+        Ok(())
+    }
+
+    /// Show text on the display
+    ///
+    /// The text is padded or cut to the display width. `cursor` is the
+    /// 0-based cell to mark with the cursor, if any.
+    pub fn write_text(&mut self, text: &str, cursor: Option<u32>) -> Result<()> {
+        let width = (self.display_size.0 * self.display_size.1.max(1)) as usize;
+        let mut cells: String = text.chars().take(width).collect();
+        let len = cells.chars().count();
+        cells.extend(std::iter::repeat(' ').take(width - len));
+
+        let flags =
+            write_flags::REGION | write_flags::TEXT | write_flags::CURSOR | write_flags::CHARSET;
+        let mut payload = flags.to_be_bytes().to_vec();
+
+        // Region is 1-based: start at the first cell, cover the whole display
+        payload.extend_from_slice(&1u32.to_be_bytes());
+        payload.extend_from_slice(&(width as u32).to_be_bytes());
+
+        payload.extend_from_slice(&(cells.len() as u32).to_be_bytes());
+        payload.extend_from_slice(cells.as_bytes());
+
+        // Cursor is 1-based, 0 hides it
+        let cursor = cursor
+            .filter(|&c| (c as usize) < width)
+            .map_or(0, |c| c + 1);
+        payload.extend_from_slice(&cursor.to_be_bytes());
+
+        let charset = b"UTF-8";
+        payload.push(charset.len() as u8);
+        payload.extend_from_slice(charset);
+
+        self.send_packet(packet::WRITE, &payload)
+    }
+
+    /// Read any key presses that have arrived without blocking
+    ///
+    /// Returns an error if the server closed the connection or
+    /// reported an error.
+    pub fn read_keys(&mut self) -> Result<Vec<u64>> {
+        let mut buf = [0u8; 256];
+        loop {
+            match self.stream.read(&mut buf) {
+                Ok(0) => {
+                    return Err(TdsrError::Braille(
+                        "BrlAPI server closed the connection".to_string(),
+                    ))
+                }
+                Ok(n) => self.pending.extend_from_slice(&buf[..n]),
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        let mut keys = Vec::new();
+        while let Some((ty, payload)) = self.take_packet()? {
+            match ty {
+                packet::KEY if payload.len() >= 8 => {
+                    let code =
+                        ((read_u32(&payload, 0) as u64) << 32) | read_u32(&payload, 4) as u64;
+                    debug!("BrlAPI key code 0x{:016x}", code);
+                    keys.push(code);
+                }
+                // Late acknowledgements for writes need no action
+                packet::ACK => {}
+                other => return Err(unexpected_packet(other, &payload)),
+            }
+        }
+        Ok(keys)
+    }
+
+    /// Release the display back to brltty
+    pub fn leave_tty_mode(&mut self) -> Result<()> {
+        self.send_packet(packet::LEAVE_TTY_MODE, &[])
+    }
+
+    /// Send one packet
+    fn send_packet(&mut self, ty: u32, payload: &[u8]) -> Result<()> {
+        let mut data = Vec::with_capacity(8 + payload.len());
+        data.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        data.extend_from_slice(&ty.to_be_bytes());
+        data.extend_from_slice(payload);
+        self.stream.write_all(&data)?;
+        Ok(())
+    }
+
+    /// Wait for an acknowledgement packet
+    fn expect_ack(&mut self) -> Result<()> {
+        let (ty, payload) = self.read_packet_blocking()?;
+        if ty == packet::ACK {
+            Ok(())
+        } else {
+            Err(unexpected_packet(ty, &payload))
+        }
+    }
+
+    /// Read one whole packet, blocking until it arrives (handshake only)
+    fn read_packet_blocking(&mut self) -> Result<(u32, Vec<u8>)> {
+        loop {
+            if let Some(packet) = self.take_packet()? {
+                return Ok(packet);
+            }
+            let mut buf = [0u8; 256];
+            let n = self.stream.read(&mut buf)?;
+            if n == 0 {
+                return Err(TdsrError::Braille(
+                    "BrlAPI server closed the connection".to_string(),
+                ));
+            }
+            self.pending.extend_from_slice(&buf[..n]);
+        }
+    }
+
+    /// Split one complete packet off the receive buffer, if there is one
+    fn take_packet(&mut self) -> Result<Option<(u32, Vec<u8>)>> {
+        if self.pending.len() < 8 {
+            return Ok(None);
+        }
+        let size = read_u32(&self.pending, 0) as usize;
+        if size > MAX_PACKET_SIZE {
+            return Err(TdsrError::Braille(format!(
+                "BrlAPI packet too large ({} bytes)",
+                size
+            )));
+        }
+        if self.pending.len() < 8 + size {
+            return Ok(None);
+        }
+        let ty = read_u32(&self.pending, 4);
+        let payload = self.pending[8..8 + size].to_vec();
+        self.pending.drain(..8 + size);
+        Ok(Some((ty, payload)))
+    }
+}
+
+impl AsRawFd for BrlapiConnection {
+    fn as_raw_fd(&self) -> RawFd {
+        self.stream.as_raw_fd()
+    }
+}
+
+/// Read a big-endian u32 at `offset`
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    let mut bytes = [0u8; 4];
+    bytes.copy_from_slice(&data[offset..offset + 4]);
+    u32::from_be_bytes(bytes)
+}
+
+/// Build an error for a packet we didn't expect
+fn unexpected_packet(ty: u32, payload: &[u8]) -> TdsrError {
+    match ty {
+        packet::ERROR if payload.len() >= 4 => {
+            TdsrError::Braille(format!("BrlAPI error {}", read_u32(payload, 0)))
+        }
+        packet::EXCEPTION => TdsrError::Braille("BrlAPI exception".to_string()),
+        _ => TdsrError::Braille(format!(
+            "Unexpected BrlAPI packet '{}'",
+            char::from_u32(ty).unwrap_or('?')
+        )),
+    }
+}
@@ -0,0 +1,319 @@
+//! Refreshable braille display output
+//!
+//! Shows the line under the review cursor on a braille display through
+//! brltty's BrlAPI. The display window always contains the review cursor,
+//! so any review command also moves what is shown in braille. Display keys
+//! are translated into the same actions the keyboard bindings use.
+
+pub mod brlapi;
+
+use crate::input::KeyAction;
+use crate::terminal::Screen;
+use crate::Result;
+use brlapi::{key, BrlapiConnection};
+use log::debug;
+use std::path::Path;
+
+/// What a braille display key asks the screen reader to do
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BrailleCommand {
+    /// Run a regular review action
+    Action(KeyAction),
+
+    /// Move the display window left, wrapping to the previous line
+    PanLeft,
+
+    /// Move the display window right, wrapping to the next line
+    PanRight,
+
+    /// Cursor routing key over the given cell of the display
+    Route(u16),
+}
+
+impl BrailleCommand {
+    /// Translate a BrlAPI key code into a command
+    ///
+    /// Returns None for keys we have no use for.
+    pub fn from_key_code(code: u64) -> Option<Self> {
+        if code & key::TYPE_MASK != key::TYPE_CMD {
+            return None;
+        }
+
+        let block = (code & key::CMD_BLK_MASK) >> key::CMD_BLK_SHIFT;
+        let arg = code & key::CMD_ARG_MASK;
+
+        if block == key::BLK_ROUTE {
+            return Some(Self::Route(arg as u16));
+        }
+        if block != 0 {
+            return None;
+        }
+
+        let command = match arg {
+            key::CMD_LNUP | key::CMD_WINUP | key::CMD_PRDIFLN => Self::Action(KeyAction::PrevLine),
+            key::CMD_LNDN | key::CMD_WINDN | key::CMD_NXDIFLN => Self::Action(KeyAction::NextLine),
+            key::CMD_TOP | key::CMD_TOP_LEFT => Self::Action(KeyAction::TopOfScreen),
+            key::CMD_BOT | key::CMD_BOT_LEFT => Self::Action(KeyAction::BottomOfScreen),
+            key::CMD_CHRLT => Self::Action(KeyAction::PrevChar),
+            key::CMD_CHRRT => Self::Action(KeyAction::NextChar),
+            key::CMD_LNBEG => Self::Action(KeyAction::StartOfLine),
+            key::CMD_LNEND => Self::Action(KeyAction::EndOfLine),
+            key::CMD_HWINLT | key::CMD_FWINLT => Self::PanLeft,
+            key::CMD_HWINRT | key::CMD_FWINRT => Self::PanRight,
+            _ => return None,
+        };
+        Some(command)
+    }
+}
+
+/// A connected braille display
+pub struct BrailleDisplay {
+    /// Connection to brltty
+    conn: BrlapiConnection,
+
+    /// Number of cells on the display
+    width: u16,
+
+    /// Last text and cursor written, to skip redundant writes
+    last_written: Option<(String, Option<u32>)>,
+}
+
+impl BrailleDisplay {
+    /// Connect to brltty and claim the display for our tty
+    pub fn connect(socket: &Path, key_file: &Path, tty: Option<u32>) -> Result<Self> {
+        let mut conn = BrlapiConnection::connect(socket, key_file)?;
+
+        let (cols, rows) = conn.display_size();
+        let width = (cols * rows.max(1)).min(u16::MAX as u32) as u16;
+        if width == 0 {
+            return Err(crate::TdsrError::Braille(
+                "No braille display attached".to_string(),
+            ));
+        }
+
+        let ttys = tty.map_or_else(controlling_ttys, |tty| vec![tty]);
+        debug!("Claiming braille display for ttys {:?}", ttys);
+        conn.enter_tty_mode(&ttys)?;
+
+        Ok(Self {
+            conn,
+            width,
+            last_written: None,
+        })
+    }
+
+    /// Number of cells on the display
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    /// File descriptor to poll for display key presses
+    pub fn as_raw_fd(&self) -> std::os::unix::io::RawFd {
+        use std::os::unix::io::AsRawFd;
+        self.conn.as_raw_fd()
+    }
+
+    /// Show the window of the screen containing the review cursor
+    pub fn show(&mut self, screen: &Screen, pos: (u16, u16)) -> Result<()> {
+        let (text, cursor) = window_text(screen, pos, self.width);
+        let cursor = Some(cursor as u32);
+
+        if self
+            .last_written
+            .as_ref()
+            .is_some_and(|(last_text, last_cursor)| *last_text == text && *last_cursor == cursor)
+        {
+            return Ok(());
+        }
+
+        self.conn.write_text(&text, cursor)?;
+        self.last_written = Some((text, cursor));
+        Ok(())
+    }
+
+    /// Read commands from display keys pressed since the last call
+    pub fn read_commands(&mut self) -> Result<Vec<BrailleCommand>> {
+        Ok(self
+            .conn
+            .read_keys()?
+            .into_iter()
+            .filter_map(BrailleCommand::from_key_code)
+            .collect())
+    }
+}
+
+impl Drop for BrailleDisplay {
+    fn drop(&mut self) {
+        if let Err(e) = self.conn.leave_tty_mode() {
+            debug!("Failed to release braille display: {}", e);
+        }
+    }
+}
+
+/// First column of the display window containing column `x`
+pub fn window_start(x: u16, width: u16) -> u16 {
+    (x / width) * width
+}
+
+/// Text of the display window containing the review cursor
+///
+/// Returns the text and the cursor offset within it. Wide character
+/// continuation cells are shown as blanks so cells line up with columns.
+pub fn window_text(screen: &Screen, pos: (u16, u16), width: u16) -> (String, u16) {
+    let (x, y) = pos;
+    let start = window_start(x, width);
+    let text = screen
+        .get_line(y)
+        .chars()
+        .skip(start as usize)
+        .take(width as usize)
+        .map(|ch| if ch == '\0' { ' ' } else { ch })
+        .collect();
+    (text, x - start)
+}
+
+/// Review position after panning right
+///
+/// Moves to the next window of the line, or the start of the next line
+/// once the rest of the current line is blank. Returns None at the end
+/// of the screen.
+pub fn pan_right(screen: &Screen, pos: (u16, u16), width: u16) -> Option<(u16, u16)> {
+    let (x, y) = pos;
+    let next = window_start(x, width).saturating_add(width);
+    let line_len = screen.get_line_trimmed(y).chars().count();
+
+    if (next as usize) < line_len {
+        Some((next, y))
+    } else if y + 1 < screen.size.1 {
+        Some((0, y + 1))
+    } else {
+        None
+    }
+}
+
+/// Review position after panning left
+///
+/// Moves to the previous window of the line, or the last non-blank window
+/// of the previous line. Returns None at the top of the screen.
+pub fn pan_left(screen: &Screen, pos: (u16, u16), width: u16) -> Option<(u16, u16)> {
+    let (x, y) = pos;
+    let start = window_start(x, width);
+
+    if start > 0 {
+        Some((start - width, y))
+    } else if y > 0 {
+        let prev_len = screen.get_line_trimmed(y - 1).chars().count() as u16;
+        Some((window_start(prev_len.saturating_sub(1), width), y - 1))
+    } else {
+        None
+    }
+}
+
+/// Review column under a cursor routing key
+pub fn route_column(pos: (u16, u16), width: u16, cell: u16, cols: u16) -> Option<u16> {
+    let column = window_start(pos.0, width).checked_add(cell)?;
+    (column < cols).then_some(column)
+}
+
+/// Work out which ttys brltty should give us the display for
+///
+/// On a text console this is the virtual terminal number. Under X the
+/// WINDOWPATH variable lists the VT and window path instead.
+fn controlling_ttys() -> Vec<u32> {
+    if let Ok(path) = std::env::var("WINDOWPATH") {
+        let ttys: Vec<u32> = path.split(':').filter_map(|p| p.parse().ok()).collect();
+        if !ttys.is_empty() {
+            return ttys;
+        }
+    }
+
+    if let Some(vt) = std::env::var("CONTROLVT")
+        .ok()
+        .and_then(|vt| vt.parse().ok())
+    {
+        return vec![vt];
+    }
+
+    nix::unistd::ttyname(std::io::stdin())
+        .ok()
+        .and_then(|path| vt_number(&path.to_string_lossy()))
+        .map_or_else(Vec::new, |vt| vec![vt])
+}
+
+/// Virtual terminal number of a console device path like /dev/tty3
+fn vt_number(path: &str) -> Option<u32> {
+    path.strip_prefix("/dev/tty")?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn screen_with_lines(cols: u16, lines: &[&str]) -> Screen {
+        let mut screen = Screen::new(cols, lines.len() as u16);
+        for (y, line) in lines.iter().enumerate() {
+            for (x, ch) in line.chars().enumerate() {
+                screen.buffer[y][x].data = ch;
+            }
+        }
+        screen
+    }
+
+    #[test]
+    fn test_window_follows_review_cursor() {
+        let screen = screen_with_lines(20, &["abcdefghijklmnopqrst"]);
+        assert_eq!(window_text(&screen, (3, 0), 8), ("abcdefgh".to_string(), 3));
+        assert_eq!(window_text(&screen, (9, 0), 8), ("ijklmnop".to_string(), 1));
+        assert_eq!(window_text(&screen, (17, 0), 8), ("qrst".to_string(), 1));
+    }
+
+    #[test]
+    fn test_pan_right_wraps_to_next_line() {
+        let screen = screen_with_lines(20, &["abcdefghijkl", "second"]);
+        assert_eq!(pan_right(&screen, (2, 0), 8), Some((8, 0)));
+        // Rest of the line is blank - go to the next line
+        assert_eq!(pan_right(&screen, (8, 0), 8), Some((0, 1)));
+        // Nothing after the last line
+        assert_eq!(pan_right(&screen, (0, 1), 8), None);
+    }
+
+    #[test]
+    fn test_pan_left_wraps_to_previous_line() {
+        let screen = screen_with_lines(20, &["abcdefghijkl", "second"]);
+        assert_eq!(pan_left(&screen, (10, 0), 8), Some((0, 0)));
+        // Start of line - go to the last window of the previous line
+        assert_eq!(pan_left(&screen, (3, 1), 8), Some((8, 0)));
+        assert_eq!(pan_left(&screen, (0, 0), 8), None);
+    }
+
+    #[test]
+    fn test_route_column() {
+        assert_eq!(route_column((10, 0), 8, 3, 20), Some(11));
+        assert_eq!(route_column((17, 0), 8, 7, 20), None);
+    }
+
+    #[test]
+    fn test_key_codes() {
+        let cmd = |arg: u64| key::TYPE_CMD | arg;
+        assert_eq!(
+            BrailleCommand::from_key_code(cmd(key::CMD_LNUP)),
+            Some(BrailleCommand::Action(KeyAction::PrevLine))
+        );
+        assert_eq!(
+            BrailleCommand::from_key_code(cmd(key::CMD_FWINRT)),
+            Some(BrailleCommand::PanRight)
+        );
+        assert_eq!(
+            BrailleCommand::from_key_code(cmd((key::BLK_ROUTE << key::CMD_BLK_SHIFT) | 5)),
+            Some(BrailleCommand::Route(5))
+        );
+        // Typed characters are not commands
+        assert_eq!(BrailleCommand::from_key_code(0x61), None);
+    }
+
+    #[test]
+    fn test_vt_number() {
+        assert_eq!(vt_number("/dev/tty3"), Some(3));
+        assert_eq!(vt_number("/dev/pts/1"), None);
+    }
+}
@@ -0,0 +1,403 @@
+//! Clipboard backends
+//!
+//! Each backend reports failures with a short reason, since the reason is
+//! spoken to the user.
+
+use super::{append_text, ClipboardBackend};
+use crate::{Result, TdsrError};
+use log::debug;
+use nix::fcntl::{fcntl, FcntlArg, OFlag};
+use std::fs;
+use std::io::{self, Read, Write};
+use std::os::unix::io::AsRawFd;
+use std::path::PathBuf;
+use std::process::{ChildStderr, Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// System clipboard through arboard (X11, Wayland, macOS, Windows)
+pub struct ArboardClipboard;
+
+impl ArboardClipboard {
+    fn open() -> Result<arboard::Clipboard> {
+        arboard::Clipboard::new()
+            .map_err(|e| TdsrError::Other(format!("no system clipboard, {}", e)))
+    }
+}
+
+impl ClipboardBackend for ArboardClipboard {
+    fn name(&self) -> &'static str {
+        "arboard"
+    }
+
+    fn copy(&mut self, text: &str) -> Result<()> {
+        debug!("Copying {} chars to the system clipboard", text.len());
+        Self::open()?
+            .set_text(text)
+            .map_err(|e| TdsrError::Other(format!("system clipboard refused, {}", e)))
+    }
+
+    fn paste(&mut self) -> Result<String> {
+        Self::open()?
+            .get_text()
+            .map_err(|e| TdsrError::Other(format!("system clipboard unreadable, {}", e)))
+    }
+}
+
+/// OSC 52 escape sequence written to the terminal TDSR runs in
+///
+/// The terminal on the user's side sets its clipboard, which works over
+/// SSH. Terminals don't let programs read the clipboard back this way, so
+/// appending adds to the text this backend last copied.
+#[derive(Default)]
+pub struct Osc52Clipboard {
+    last: Option<String>,
+}
+
+impl ClipboardBackend for Osc52Clipboard {
+    fn name(&self) -> &'static str {
+        "osc52"
+    }
+
+    fn copy(&mut self, text: &str) -> Result<()> {
+        debug!("Copying {} chars with OSC 52", text.len());
+        let mut stdout = io::stdout();
+        stdout.write_all(&osc52_sequence(text))?;
+        stdout.flush()?;
+        self.last = Some(text.to_string());
+        Ok(())
+    }
+
+    fn paste(&mut self) -> Result<String> {
+        Err(TdsrError::Other(
+            "OSC 52 can't read the clipboard".to_string(),
+        ))
+    }
+
+    fn append(&mut self, text: &str) -> Result<()> {
+        let joined = append_to_last(&self.last, text)?;
+        self.copy(&joined)
+    }
+}
+
+/// Join text onto what a write-only backend last copied
+fn append_to_last(last: &Option<String>, text: &str) -> Result<String> {
+    match last {
+        Some(previous) => Ok(append_text(previous, text)),
+        None => Err(TdsrError::Other(
+            "nothing copied yet to append to".to_string(),
+        )),
+    }
+}
+
+/// Build the OSC 52 sequence that sets the clipboard to `text`
+pub fn osc52_sequence(text: &str) -> Vec<u8> {
+    format!("\x1b]52;c;{}\x07", base64(text.as_bytes())).into_bytes()
+}
+
+/// Standard base64 with padding
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity((data.len() + 2) / 3 * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(bytes[0]) << 16) | (u32::from(bytes[1]) << 8) | u32::from(bytes[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// tmux paste buffer, through `tmux load-buffer` and `tmux save-buffer`
+pub struct TmuxClipboard;
+
+impl ClipboardBackend for TmuxClipboard {
+    fn name(&self) -> &'static str {
+        "tmux"
+    }
+
+    fn copy(&mut self, text: &str) -> Result<()> {
+        debug!("Copying {} chars to a tmux buffer", text.len());
+        run(
+            Command::new("tmux").args(["load-buffer", "-"]),
+            "tmux",
+            text,
+            false,
+        )
+        .map(|_| ())
+    }
+
+    fn paste(&mut self) -> Result<String> {
+        run(
+            Command::new("tmux").args(["save-buffer", "-"]),
+            "tmux",
+            "",
+            true,
+        )
+    }
+}
+
+/// A plain file holding the clipboard content
+pub struct FileClipboard {
+    path: PathBuf,
+}
+
+impl FileClipboard {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+impl ClipboardBackend for FileClipboard {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    fn copy(&mut self, text: &str) -> Result<()> {
+        debug!("Copying {} chars to {}", text.len(), self.path.display());
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(|e| file_error("can't create folder", e))?;
+        }
+        fs::write(&self.path, text).map_err(|e| file_error("can't write file", e))
+    }
+
+    fn paste(&mut self) -> Result<String> {
+        fs::read_to_string(&self.path).map_err(|e| file_error("can't read file", e))
+    }
+}
+
+fn file_error(what: &str, e: io::Error) -> TdsrError {
+    TdsrError::Other(format!("{}, {}", what, e))
+}
+
+/// A shell command that receives copied text on its input
+/// e.g. `xclip -selection clipboard` or `wl-copy`
+///
+/// The command can't be read back, so appending adds to the text this
+/// backend last copied.
+pub struct CommandClipboard {
+    command: String,
+    last: Option<String>,
+}
+
+impl CommandClipboard {
+    pub fn new(command: String) -> Self {
+        Self {
+            command,
+            last: None,
+        }
+    }
+}
+
+impl ClipboardBackend for CommandClipboard {
+    fn name(&self) -> &'static str {
+        "command"
+    }
+
+    fn copy(&mut self, text: &str) -> Result<()> {
+        if self.command.trim().is_empty() {
+            return Err(TdsrError::Other("no clipboard command set".to_string()));
+        }
+        debug!("Copying {} chars with '{}'", text.len(), self.command);
+        run(
+            Command::new("sh").args(["-c", &self.command]),
+            "clipboard command",
+            text,
+            false,
+        )?;
+        self.last = Some(text.to_string());
+        Ok(())
+    }
+
+    fn paste(&mut self) -> Result<String> {
+        Err(TdsrError::Other(
+            "clipboard command can't be read back".to_string(),
+        ))
+    }
+
+    fn append(&mut self, text: &str) -> Result<()> {
+        let joined = append_to_last(&self.last, text)?;
+        self.copy(&joined)
+    }
+}
+
+/// How long a clipboard command may take before it is given up on
+///
+/// The main loop waits on the command, so terminal output and keys stall
+/// until it finishes. Clipboard tools normally exit within a few
+/// milliseconds; this only bounds a hung or misconfigured one.
+const COMMAND_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// How often to check whether a clipboard command has exited
+const COMMAND_POLL: Duration = Duration::from_millis(2);
+
+/// Run a command with `input` on stdin, returning its output if `capture`
+///
+/// Commands such as `xclip` leave a child behind that holds the clipboard
+/// and inherits the command's pipes, so nothing here waits for the pipes
+/// to close: input is written from a thread, output is collected from a
+/// thread with a deadline, and stderr is only read once the command has
+/// exited, without blocking. A command that hangs is killed after
+/// `COMMAND_TIMEOUT`.
+fn run(command: &mut Command, name: &str, input: &str, capture: bool) -> Result<String> {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(if capture {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| TdsrError::Other(format!("can't run {}, {}", name, e)))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        let input = input.as_bytes().to_vec();
+        thread::spawn(move || {
+            // A command that exits without reading its input is judged by its status
+            let _ = stdin.write_all(&input);
+        });
+    }
+
+    let output = child.stdout.take().map(|mut stdout| {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let mut text = String::new();
+            let _ = stdout.read_to_string(&mut text);
+            let _ = tx.send(text);
+        });
+        rx
+    });
+
+    let deadline = Instant::now() + COMMAND_TIMEOUT;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(TdsrError::Other(format!("{} timed out", name)));
+        }
+        thread::sleep(COMMAND_POLL);
+    };
+
+    if !status.success() {
+        let stderr = child.stderr.take().map(read_available).unwrap_or_default();
+        let reason = stderr.lines().next().unwrap_or("").trim();
+        return Err(TdsrError::Other(if reason.is_empty() {
+            format!("{} failed", name)
+        } else {
+            format!("{} failed, {}", name, reason)
+        }));
+    }
+
+    match output {
+        Some(rx) => {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            rx.recv_timeout(remaining)
+                .map_err(|_| TdsrError::Other(format!("{} timed out", name)))
+        }
+        None => Ok(String::new()),
+    }
+}
+
+/// Read whatever a finished command left on stderr, without waiting for
+/// children that may still hold the pipe open
+fn read_available(mut stderr: ChildStderr) -> String {
+    let mut bytes = Vec::new();
+    if fcntl(stderr.as_raw_fd(), FcntlArg::F_SETFL(OFlag::O_NONBLOCK)).is_ok() {
+        let mut chunk = [0u8; 1024];
+        while let Ok(n) = stderr.read(&mut chunk) {
+            if n == 0 {
+                break;
+            }
+            bytes.extend_from_slice(&chunk[..n]);
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64("héllo\n".as_bytes()), "aMOpbGxvCg==");
+    }
+
+    #[test]
+    fn test_osc52_sequence() {
+        assert_eq!(osc52_sequence("hi"), b"\x1b]52;c;aGk=\x07".to_vec());
+    }
+
+    #[test]
+    fn test_file_clipboard() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut clipboard = FileClipboard::new(dir.path().join("tdsr").join("clipboard"));
+
+        clipboard.copy("one").unwrap();
+        clipboard.append("two").unwrap();
+        assert_eq!(clipboard.paste().unwrap(), "one\ntwo");
+    }
+
+    #[test]
+    fn test_command_clipboard_reports_reason() {
+        let mut clipboard = CommandClipboard::new("echo nope >&2; exit 1".to_string());
+        let err = clipboard.copy("text").unwrap_err();
+        assert_eq!(err.to_string(), "clipboard command failed, nope");
+
+        let mut unset = CommandClipboard::new(String::new());
+        assert_eq!(
+            unset.copy("text").unwrap_err().to_string(),
+            "no clipboard command set"
+        );
+    }
+
+    #[test]
+    fn test_command_clipboard_appends_to_last_copy() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("clipboard");
+        let mut clipboard = CommandClipboard::new(format!("cat > {}", path.display()));
+
+        assert_eq!(
+            clipboard.append("one").unwrap_err().to_string(),
+            "nothing copied yet to append to"
+        );
+        clipboard.copy("one").unwrap();
+        clipboard.append("two").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "one\ntwo");
+    }
+
+    #[test]
+    fn test_command_clipboard_does_not_wait_for_children() {
+        // Like xclip, which leaves a child holding the clipboard and the pipes
+        let mut lingering = CommandClipboard::new("sleep 5 &".to_string());
+        let start = Instant::now();
+        lingering.copy("text").unwrap();
+        assert!(start.elapsed() < COMMAND_TIMEOUT);
+
+        let mut hung = CommandClipboard::new("exec sleep 5".to_string());
+        assert_eq!(
+            hung.copy("text").unwrap_err().to_string(),
+            "clipboard command timed out"
+        );
+    }
+}
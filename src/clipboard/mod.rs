@@ -0,0 +1,268 @@
+//! Clipboard integration
+//!
+//! Copied text goes to one of several backends. The system clipboard
+//! (through arboard) needs X11 or Wayland, so headless and SSH sessions
+//! use OSC 52 to the outer terminal, a tmux buffer, a plain file or a
+//! custom command instead.
+
+pub mod backends;
+
+use crate::state::config::Config;
+use crate::Result;
+use log::{debug, info, warn};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// A place copied text can be sent to
+pub trait ClipboardBackend {
+    /// Name of the backend, as written in the config
+    fn name(&self) -> &'static str;
+
+    /// Replace the clipboard content
+    fn copy(&mut self, text: &str) -> Result<()>;
+
+    /// Read the clipboard content
+    fn paste(&mut self) -> Result<String>;
+
+    /// Add text to the end of the clipboard content
+    ///
+    /// Backends that can't read the clipboard back override this.
+    fn append(&mut self, text: &str) -> Result<()> {
+        // An empty clipboard, or one holding something other than text, starts over
+        let previous = self.paste().unwrap_or_default();
+        self.copy(&append_text(&previous, text))
+    }
+}
+
+/// Clipboard backends that can be configured
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackendKind {
+    Arboard,
+    Osc52,
+    Tmux,
+    File,
+    Command,
+}
+
+impl BackendKind {
+    /// Parse a backend name from the config; None for "auto" or unknown names
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "arboard" | "system" => Some(Self::Arboard),
+            "osc52" => Some(Self::Osc52),
+            "tmux" => Some(Self::Tmux),
+            "file" => Some(Self::File),
+            "command" => Some(Self::Command),
+            _ => None,
+        }
+    }
+
+    /// Pick a backend from the environment
+    ///
+    /// Inside tmux the tmux buffer is used. Over SSH, or on Linux without
+    /// a display server, the system clipboard is out of reach, so OSC 52
+    /// asks the terminal on the user's side to set its clipboard. The
+    /// Linux console and unknown terminals ignore OSC 52, so a file is
+    /// used there instead of losing copies silently.
+    pub fn detect(var: impl Fn(&str) -> Option<String>, os: &str) -> Self {
+        let has_var = |name: &str| var(name).is_some_and(|value| !value.is_empty());
+        let ssh = has_var("SSH_CONNECTION") || has_var("SSH_TTY");
+        let no_display = os == "linux" && !has_var("DISPLAY") && !has_var("WAYLAND_DISPLAY");
+        let osc52_terminal = match var("TERM").as_deref() {
+            None | Some("") | Some("linux") | Some("dumb") => false,
+            Some(_) => true,
+        };
+
+        if has_var("TMUX") {
+            Self::Tmux
+        } else if !(ssh || no_display) {
+            Self::Arboard
+        } else if osc52_terminal {
+            Self::Osc52
+        } else {
+            Self::File
+        }
+    }
+}
+
+/// Create the clipboard backend chosen in the config, detecting it for "auto"
+pub fn create_backend(config: &Config) -> Box<dyn ClipboardBackend> {
+    let name = config.clipboard_backend();
+    let kind = match BackendKind::parse(&name) {
+        Some(kind) => kind,
+        None => {
+            if !name.trim().eq_ignore_ascii_case("auto") {
+                warn!("Unknown clipboard backend '{}', detecting one", name);
+            }
+            BackendKind::detect(|var| std::env::var(var).ok(), std::env::consts::OS)
+        }
+    };
+
+    let backend: Box<dyn ClipboardBackend> = match kind {
+        BackendKind::Arboard => Box::new(backends::ArboardClipboard),
+        BackendKind::Osc52 => Box::new(backends::Osc52Clipboard::default()),
+        BackendKind::Tmux => Box::new(backends::TmuxClipboard),
+        BackendKind::File => Box::new(backends::FileClipboard::new(config.clipboard_file())),
+        BackendKind::Command => {
+            Box::new(backends::CommandClipboard::new(config.clipboard_command()))
+        }
+    };
+    info!("Using {} clipboard backend", backend.name());
+    backend
+}
+
+/// Join new text onto previous content, on a line of its own
+pub fn append_text(previous: &str, text: &str) -> String {
+    if previous.is_empty() {
+        text.to_string()
+    } else if previous.ends_with('\n') {
+        format!("{}{}", previous, text)
+    } else {
+        format!("{}\n{}", previous, text)
+    }
+}
+
+/// Save text to a numbered file (copy-1.txt, copy-2.txt, ...) in a directory
+///
+/// Each save goes to a new file; when appending, text is added to the
+/// newest file instead. The directory is created if needed. Returns the
+/// path written.
+pub fn save_to_file(dir: &Path, text: &str, append: bool) -> Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+
+    let mut names = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        if let Some(name) = entry?.file_name().to_str() {
+            names.push(name.to_string());
+        }
+    }
+    let newest = names.iter().filter_map(|name| copy_file_number(name)).max();
+
+    let (number, append) = match newest {
+        Some(n) if append => (n, true),
+        Some(n) => (n + 1, false),
+        None => (1, false),
+    };
+    let path = dir.join(copy_file_name(number));
+    debug!("Saving {} chars to {}", text.len(), path.display());
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(append)
+        .truncate(!append)
+        .open(&path)?;
+    file.write_all(text.as_bytes())?;
+    file.write_all(b"\n")?;
+
+    Ok(path)
+}
+
+fn copy_file_name(number: u32) -> String {
+    format!("copy-{}.txt", number)
+}
+
+fn copy_file_number(name: &str) -> Option<u32> {
+    name.strip_prefix("copy-")?
+        .strip_suffix(".txt")?
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_backend() {
+        assert_eq!(BackendKind::parse("osc52"), Some(BackendKind::Osc52));
+        assert_eq!(BackendKind::parse(" Tmux "), Some(BackendKind::Tmux));
+        assert_eq!(BackendKind::parse("system"), Some(BackendKind::Arboard));
+        assert_eq!(BackendKind::parse("auto"), None);
+        assert_eq!(BackendKind::parse("xclip"), None);
+    }
+
+    #[test]
+    fn test_detect_backend() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(var, _)| *var == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+
+        assert_eq!(
+            BackendKind::detect(
+                env(&[("TMUX", "/tmp/tmux"), ("SSH_TTY", "/dev/pts/1")]),
+                "linux"
+            ),
+            BackendKind::Tmux
+        );
+        assert_eq!(
+            BackendKind::detect(
+                env(&[
+                    ("SSH_CONNECTION", "1 2 3 4"),
+                    ("DISPLAY", ":0"),
+                    ("TERM", "xterm")
+                ]),
+                "linux"
+            ),
+            BackendKind::Osc52
+        );
+        assert_eq!(
+            BackendKind::detect(env(&[("TERM", "xterm-256color")]), "linux"),
+            BackendKind::Osc52
+        );
+        assert_eq!(
+            BackendKind::detect(
+                env(&[("WAYLAND_DISPLAY", "wayland-0"), ("TERM", "linux")]),
+                "linux"
+            ),
+            BackendKind::Arboard
+        );
+        assert_eq!(BackendKind::detect(env(&[]), "macos"), BackendKind::Arboard);
+
+        // The Linux console and unknown terminals ignore OSC 52
+        assert_eq!(
+            BackendKind::detect(env(&[("TERM", "linux")]), "linux"),
+            BackendKind::File
+        );
+        assert_eq!(BackendKind::detect(env(&[]), "linux"), BackendKind::File);
+        assert_eq!(
+            BackendKind::detect(env(&[("SSH_TTY", "/dev/pts/1"), ("TERM", "dumb")]), "linux"),
+            BackendKind::File
+        );
+    }
+
+    #[test]
+    fn test_append_text() {
+        assert_eq!(append_text("", "two"), "two");
+        assert_eq!(append_text("one", "two"), "one\ntwo");
+        assert_eq!(append_text("one\n", "two"), "one\ntwo");
+    }
+
+    #[test]
+    fn test_copy_file_number() {
+        assert_eq!(copy_file_number(&copy_file_name(12)), Some(12));
+        assert_eq!(copy_file_number("copy-x.txt"), None);
+        assert_eq!(copy_file_number("notes.txt"), None);
+    }
+
+    #[test]
+    fn test_save_to_file() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let dir = temp.path().join("copies");
+
+        let first = save_to_file(&dir, "one", false).unwrap();
+        assert_eq!(first, dir.join("copy-1.txt"));
+        let appended = save_to_file(&dir, "two", true).unwrap();
+        assert_eq!(appended, first);
+        assert_eq!(fs::read_to_string(&first).unwrap(), "one\ntwo\n");
+
+        let second = save_to_file(&dir, "three", false).unwrap();
+        assert_eq!(second, dir.join("copy-2.txt"));
+        assert_eq!(fs::read_to_string(&second).unwrap(), "three\n");
+    }
+}
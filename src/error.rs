@@ -21,6 +21,9 @@ pub enum TdsrError {
     #[error("Plugin error: {0}")]
     Plugin(String),
 
+    #[error("Braille error: {0}")]
+    Braille(String),
+
     #[error("I/O error: {0}")]
     Io(#[from] io::Error),
 
@@ -0,0 +1,78 @@
+//! Bookmark handler
+//!
+//! Modal handler that reads a bookmark name after alt+', alt+g or alt+G.
+
+use super::{HandlerAction, KeyHandler};
+use crate::review::bookmarks::Bookmarks;
+use crate::state::State;
+use crate::terminal::Emulator;
+use crate::Result;
+use log::debug;
+
+/// What to do with the named bookmark
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookmarkAction {
+    /// Set it at the review cursor
+    Set,
+    /// Move the review cursor to it and read the line
+    Jump,
+    /// Read its line without moving
+    Read,
+}
+
+/// Bookmark name key handler
+///
+/// Takes the next key as a bookmark name (a letter or digit).
+/// Any other key cancels.
+pub struct BookmarkHandler {
+    action: BookmarkAction,
+}
+
+impl BookmarkHandler {
+    /// Create a handler for the given bookmark action
+    pub fn new(action: BookmarkAction) -> Self {
+        Self { action }
+    }
+
+    /// Process the bookmark name key
+    pub fn process_with_state(
+        &mut self,
+        key: &[u8],
+        state: &mut State,
+        emulator: &mut Emulator,
+    ) -> Result<HandlerAction> {
+        let name = match key {
+            [byte] if Bookmarks::is_valid_name(*byte as char) => *byte as char,
+            _ => {
+                debug!("Bookmark: not a bookmark name, cancelling");
+                state.speak("cancelled")?;
+                return Ok(HandlerAction::Remove);
+            }
+        };
+
+        debug!("Bookmark {:?} '{}'", self.action, name);
+        let screen = emulator.screen();
+        match self.action {
+            BookmarkAction::Set => state.set_bookmark(name)?,
+            BookmarkAction::Jump => state.jump_to_bookmark(name, screen)?,
+            BookmarkAction::Read => state.read_bookmark(name, screen)?,
+        }
+        Ok(HandlerAction::Remove)
+    }
+}
+
+impl KeyHandler for BookmarkHandler {
+    fn process(&mut self, _key: &[u8]) -> Result<HandlerAction> {
+        // This shouldn't be called directly - use process_with_context instead
+        Ok(HandlerAction::Handled)
+    }
+
+    fn process_with_context(
+        &mut self,
+        key: &[u8],
+        state: &mut State,
+        emulator: &mut Emulator,
+    ) -> Result<HandlerAction> {
+        self.process_with_state(key, state, emulator)
+    }
+}
@@ -5,13 +5,22 @@
 
 use super::{HandlerAction, KeyHandler};
 use crate::state::State;
-use crate::terminal::Emulator;
+use crate::terminal::{Emulator, Screen};
 use crate::Result;
 use log::debug;
 
 /// Callback function type for when input is complete
 type OnAcceptFn = Box<dyn FnOnce(String, &mut State) -> Result<()> + Send>;
 
+/// Callback for input that acts on the screen (e.g. searching it)
+type OnAcceptScreenFn = Box<dyn FnOnce(String, &mut State, &Screen) -> Result<()> + Send>;
+
+/// What to call when input is complete
+enum OnAccept {
+    State(OnAcceptFn),
+    Screen(OnAcceptScreenFn),
+}
+
 /// Handler that collects text input until Enter is pressed
 ///
 /// Used for numeric input in config menu and other text entry scenarios.
@@ -21,7 +30,7 @@ pub struct BufferHandler {
     buffer: String,
 
     /// Callback to execute when Enter is pressed
-    on_accept: Option<OnAcceptFn>,
+    on_accept: Option<OnAccept>,
 }
 
 impl BufferHandler {
@@ -31,25 +40,49 @@ impl BufferHandler {
     pub fn new(on_accept: OnAcceptFn) -> Self {
         Self {
             buffer: String::new(),
-            on_accept: Some(on_accept),
+            on_accept: Some(OnAccept::State(on_accept)),
+        }
+    }
+
+    /// Create a buffer handler whose callback also gets the screen
+    pub fn with_screen(on_accept: OnAcceptScreenFn) -> Self {
+        Self {
+            buffer: String::new(),
+            on_accept: Some(OnAccept::Screen(on_accept)),
         }
     }
 
-    /// Process input with state access
-    pub fn process_with_state(&mut self, key: &[u8], state: &mut State) -> Result<HandlerAction> {
+    /// Process input with state and screen access
+    pub fn process_with_state(
+        &mut self,
+        key: &[u8],
+        state: &mut State,
+        screen: &Screen,
+    ) -> Result<HandlerAction> {
         match key {
             // Enter - accept input and invoke callback
             b"\r" | b"\n" => {
                 debug!("BufferHandler: accepting input '{}'", self.buffer);
 
-                if let Some(callback) = self.on_accept.take() {
-                    callback(self.buffer.clone(), state)?;
+                match self.on_accept.take() {
+                    Some(OnAccept::State(callback)) => callback(self.buffer.clone(), state)?,
+                    Some(OnAccept::Screen(callback)) => {
+                        callback(self.buffer.clone(), state, screen)?
+                    }
+                    None => {}
                 }
 
                 // Remove this handler from stack
                 Ok(HandlerAction::Remove)
             }
 
+            // Escape - cancel input without invoking callback
+            b"\x1b" => {
+                debug!("BufferHandler: cancelled");
+                state.speak("cancelled")?;
+                Ok(HandlerAction::Remove)
+            }
+
             // Backspace - remove last character
             b"\x08" | b"\x7f" => {
                 if !self.buffer.is_empty() {
@@ -83,8 +116,8 @@ impl KeyHandler for BufferHandler {
         &mut self,
         key: &[u8],
         state: &mut State,
-        _emulator: &mut Emulator,
+        emulator: &mut Emulator,
     ) -> Result<HandlerAction> {
-        self.process_with_state(key, state)
+        self.process_with_state(key, state, emulator.screen())
     }
 }
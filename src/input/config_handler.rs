@@ -16,12 +16,15 @@ use log::debug;
 /// - r: set speech rate
 /// - v: set volume
 /// - V: set voice index
-/// - p: toggle symbol processing
+/// - p: cycle symbol level
 /// - d: set cursor delay
 /// - e: toggle key echo
 /// - c: toggle cursor tracking
+/// - h: toggle highlight tracking
+/// - w: toggle sub-word navigation
 /// - l: toggle line pause
 /// - s: toggle repeated symbols
+/// - D: add a dictionary entry for the word under the review cursor
 /// - Enter: exit and save config
 pub struct ConfigHandler;
 
@@ -84,20 +87,10 @@ impl ConfigHandler {
                 Ok(HandlerAction::Handled)
             }
 
-            // Toggle process symbols
+            // Cycle symbol level
             b"p" => {
-                debug!("Config: toggle process symbols");
-                let current = state.config.process_symbols();
-                let new_value = !current;
-                state
-                    .config
-                    .set("speech", "process_symbols", &new_value.to_string());
-                state.save_config()?;
-                state.speak(if new_value {
-                    "process symbols on"
-                } else {
-                    "process symbols off"
-                })?;
+                debug!("Config: cycle symbol level");
+                state.cycle_symbol_level()?;
                 Ok(HandlerAction::Handled)
             }
 
@@ -115,20 +108,14 @@ impl ConfigHandler {
                 Ok(HandlerAction::Handled)
             }
 
-            // Toggle key echo
+            // Cycle key echo mode
             b"e" => {
-                debug!("Config: toggle key echo");
-                let current = state.config.key_echo();
-                let new_value = !current;
-                state
-                    .config
-                    .set("speech", "key_echo", &new_value.to_string());
+                debug!("Config: cycle key echo");
+                let mode = state.config.key_echo().next();
+                state.config.set("speech", "key_echo", mode.name());
                 state.save_config()?;
-                state.speak(if new_value {
-                    "key echo on"
-                } else {
-                    "key echo off"
-                })?;
+                state.key_echo.set_mode(mode);
+                state.speak(&format!("key echo {}", mode.name()))?;
                 Ok(HandlerAction::Handled)
             }
 
@@ -149,6 +136,40 @@ impl ConfigHandler {
                 Ok(HandlerAction::Handled)
             }
 
+            // Toggle highlight tracking
+            b"h" => {
+                debug!("Config: toggle highlight tracking");
+                let current = state.config.highlight_tracking();
+                let new_value = !current;
+                state
+                    .config
+                    .set("speech", "highlight_tracking", &new_value.to_string());
+                state.save_config()?;
+                state.speak(if new_value {
+                    "highlight tracking on"
+                } else {
+                    "highlight tracking off"
+                })?;
+                Ok(HandlerAction::Handled)
+            }
+
+            // Toggle sub-word navigation
+            b"w" => {
+                debug!("Config: toggle sub words");
+                let current = state.config.sub_words();
+                let new_value = !current;
+                state
+                    .config
+                    .set("speech", "sub_words", &new_value.to_string());
+                state.save_config()?;
+                state.speak(if new_value {
+                    "sub words on"
+                } else {
+                    "sub words off"
+                })?;
+                Ok(HandlerAction::Handled)
+            }
+
             // Toggle line pause
             b"l" => {
                 debug!("Config: toggle line pause");
@@ -166,6 +187,16 @@ impl ConfigHandler {
                 Ok(HandlerAction::Handled)
             }
 
+            // Cycle indentation announcement
+            b"i" => {
+                debug!("Config: cycle indentation");
+                let mode = state.config.indentation().next();
+                state.config.set("speech", "indentation", mode.name());
+                state.save_config()?;
+                state.speak(&format!("indentation {}", mode.name()))?;
+                Ok(HandlerAction::Handled)
+            }
+
             // Toggle repeated symbols
             b"s" => {
                 debug!("Config: toggle repeated symbols");
@@ -251,6 +282,16 @@ impl ConfigHandler {
         Ok(())
     }
 
+    /// Add a dictionary entry for a word from user input
+    fn add_dictionary_entry(word: &str, input: String, state: &mut State) -> Result<()> {
+        if input.trim().is_empty() {
+            debug!("Empty dictionary replacement for {}", word);
+            state.speak("invalid")?;
+            return Ok(());
+        }
+        state.add_dictionary_entry(word, input.trim())
+    }
+
     /// Set cursor delay from user input (in milliseconds)
     fn set_cursor_delay(input: String, state: &mut State) -> Result<()> {
         match input.parse::<u32>() {
@@ -280,8 +321,27 @@ impl KeyHandler for ConfigHandler {
         &mut self,
         key: &[u8],
         state: &mut State,
-        _emulator: &mut Emulator,
+        emulator: &mut Emulator,
     ) -> Result<HandlerAction> {
+        // Dictionary entries need the screen to find the word under the review cursor
+        if key == b"D" {
+            debug!("Config: add dictionary entry");
+            let word = state.word_at_review_cursor(emulator.screen());
+            if word.is_empty() {
+                state.speak("no word")?;
+                return Ok(HandlerAction::Handled);
+            }
+            state.speak(&format!("replace {}", word))?;
+            state
+                .handlers
+                .push(Box::new(super::buffer_handler::BufferHandler::new(
+                    Box::new(move |input: String, state: &mut State| {
+                        Self::add_dictionary_entry(&word, input, state)
+                    }),
+                )));
+            return Ok(HandlerAction::Handled);
+        }
+
         self.process_with_state(key, state)
     }
 }
@@ -1,9 +1,11 @@
 //! Copy mode handler
 //!
 //! Modal handler for copying screen content (alt+v)
-//! Allows copying the current line or entire screen.
+//! Allows copying the current line, word, parts of the screen and more,
+//! to the clipboard or to a file.
 
 use super::{HandlerAction, KeyHandler};
+use crate::review::selection::{linear_text, trim_rows};
 use crate::state::State;
 use crate::terminal::Emulator;
 use crate::Result;
@@ -13,9 +15,27 @@ use log::debug;
 ///
 /// When user presses alt+v, this handler intercepts keys:
 /// - l: copy current line (where review cursor is)
+/// - w: copy the word under the review cursor
+/// - e: copy from the review cursor to the end of the line
+/// - d: copy from the review cursor to the end of the screen
+/// - t: copy from the top of the screen to the review cursor
 /// - s: copy entire screen
+/// - o: copy the last command's output
+/// - h: copy the utterance selected in speech history
+/// - a: toggle appending to the previous copy, then pick what to copy
+/// - f: toggle saving to a file instead of the clipboard
 /// - Other: exit copy mode
-pub struct CopyHandler;
+pub struct CopyHandler {
+    /// Utterance selected in speech history when copy mode was entered
+    /// Captured up front because the "copy" prompt itself is recorded in history
+    utterance: Option<String>,
+
+    /// Add to the previous copy instead of replacing it
+    append: bool,
+
+    /// Save to a file in the configured directory instead of the clipboard
+    to_file: bool,
+}
 
 impl Default for CopyHandler {
     fn default() -> Self {
@@ -26,7 +46,41 @@ impl Default for CopyHandler {
 impl CopyHandler {
     /// Create a new copy handler
     pub fn new() -> Self {
-        Self
+        Self::with_utterance(None)
+    }
+
+    /// Create a copy handler that can copy the given history utterance
+    pub fn with_utterance(utterance: Option<String>) -> Self {
+        Self {
+            utterance,
+            append: false,
+            to_file: false,
+        }
+    }
+
+    /// Copy text to the chosen destination and speak `what` was copied
+    fn deliver(&self, text: &str, what: &str, state: &mut State) -> Result<()> {
+        let result = if self.to_file {
+            let dir = state.config.copy_directory();
+            crate::clipboard::save_to_file(&dir, text, self.append).map(|path| {
+                debug!("Saved {} to {}", what, path.display());
+            })
+        } else if self.append {
+            state.clipboard.append(text)
+        } else {
+            state.clipboard.copy(text)
+        };
+
+        match result {
+            Ok(()) => {
+                debug!("Copied {}: {} chars", what, text.len());
+                state.speak(what)
+            }
+            Err(e) => {
+                debug!("Failed to copy {}: {}", what, e);
+                state.speak(&format!("copy failed, {}", e))
+            }
+        }
     }
 
     /// Process copy mode keys
@@ -36,22 +90,51 @@ impl CopyHandler {
         state: &mut State,
         emulator: &mut Emulator,
     ) -> Result<HandlerAction> {
+        let (cols, rows) = emulator.screen().size;
+        let (x, y) = state.review.pos;
+
         match key {
             // Copy line where review cursor is positioned
             b"l" => {
                 debug!("Copy mode: copy line");
-                let y = state.review.pos.1;
                 let line = emulator.screen().get_line_trimmed(y);
+                self.deliver(&line, "line", state)?;
+                Ok(HandlerAction::Remove)
+            }
 
-                // Copy to clipboard
-                if let Err(e) = crate::clipboard::copy_to_clipboard(&line) {
-                    debug!("Failed to copy line: {}", e);
-                    state.speak("failed")?;
+            // Copy the word under the review cursor
+            b"w" => {
+                debug!("Copy mode: copy word");
+                let word = state.word_at_review_cursor(emulator.screen());
+                if word.is_empty() {
+                    state.speak("no word")?;
                 } else {
-                    debug!("Copied line to clipboard: '{}'", line);
-                    state.speak("line")?;
+                    self.deliver(&word, "word", state)?;
                 }
+                Ok(HandlerAction::Remove)
+            }
 
+            // Copy from the review cursor to the end of its line
+            b"e" => {
+                debug!("Copy mode: copy to end of line");
+                let text = linear_text(emulator.screen(), (x, y), (cols - 1, y));
+                self.deliver(text.trim_end(), "to end of line", state)?;
+                Ok(HandlerAction::Remove)
+            }
+
+            // Copy from the review cursor to the end of the screen
+            b"d" => {
+                debug!("Copy mode: copy to end of screen");
+                let text = linear_text(emulator.screen(), (x, y), (cols - 1, rows - 1));
+                self.deliver(&trim_rows(&text), "to end of screen", state)?;
+                Ok(HandlerAction::Remove)
+            }
+
+            // Copy from the top of the screen to the review cursor
+            b"t" => {
+                debug!("Copy mode: copy from top of screen");
+                let text = linear_text(emulator.screen(), (0, 0), (x, y));
+                self.deliver(&trim_rows(&text), "from top", state)?;
                 Ok(HandlerAction::Remove)
             }
 
@@ -61,7 +144,7 @@ impl CopyHandler {
                 let mut text = String::new();
 
                 // Collect all lines from screen
-                for y in 0..emulator.screen().size.1 {
+                for y in 0..rows {
                     let line = emulator.screen().get_line_trimmed(y);
                     if !line.is_empty() {
                         text.push_str(&line);
@@ -69,21 +152,60 @@ impl CopyHandler {
                     }
                 }
 
-                // Copy to clipboard
-                if let Err(e) = crate::clipboard::copy_to_clipboard(&text) {
-                    debug!("Failed to copy screen: {}", e);
-                    state.speak("failed")?;
-                } else {
-                    debug!(
-                        "Copied screen to clipboard: {} lines",
-                        emulator.screen().size.1
-                    );
-                    state.speak("screen")?;
+                self.deliver(&text, "screen", state)?;
+                Ok(HandlerAction::Remove)
+            }
+
+            // Copy the last command's output, including any in scrollback
+            b"o" => {
+                debug!("Copy mode: copy output");
+                match state.last_output(emulator.screen()) {
+                    Some(lines) => {
+                        self.deliver(&lines.join("\n"), "output", state)?;
+                    }
+                    None => {
+                        state.speak("no output")?;
+                    }
+                }
+
+                Ok(HandlerAction::Remove)
+            }
+
+            // Copy the utterance being reviewed in speech history
+            b"h" => {
+                debug!("Copy mode: copy utterance");
+                match self.utterance.take() {
+                    Some(text) => {
+                        self.deliver(&text, "utterance", state)?;
+                    }
+                    None => {
+                        state.speak("no history")?;
+                    }
                 }
 
                 Ok(HandlerAction::Remove)
             }
 
+            // Toggle appending; stays in copy mode to pick what to copy
+            b"a" => {
+                self.append = !self.append;
+                debug!("Copy mode: append {}", self.append);
+                state.speak(if self.append { "append" } else { "replace" })?;
+                Ok(HandlerAction::Handled)
+            }
+
+            // Toggle saving to a file; stays in copy mode to pick what to copy
+            b"f" => {
+                self.to_file = !self.to_file;
+                debug!("Copy mode: to file {}", self.to_file);
+                state.speak(if self.to_file {
+                    "to file"
+                } else {
+                    "to clipboard"
+                })?;
+                Ok(HandlerAction::Handled)
+            }
+
             // Any other key - exit copy mode
             _ => {
                 debug!("Copy mode: unknown key, exiting");
@@ -3,14 +3,20 @@
 //! Processes Alt+key combinations for screen reader navigation commands
 //! and passes unrecognized keys through to the shell.
 
+use super::bookmark_handler::{BookmarkAction, BookmarkHandler};
+use super::table_handler::TableHandler;
 use super::{HandlerAction, KeyAction, KeyHandler};
+use crate::review::search::Direction;
 use crate::state::State;
-use crate::terminal::Emulator;
+use crate::terminal::{Emulator, Screen};
 use crate::Result;
 use log::{debug, trace};
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
+/// Time within which a second press of a key counts as a double tap
+pub const DOUBLE_TAP_WINDOW: Duration = Duration::from_millis(500);
+
 /// Default key handler for screen reader commands
 ///
 /// This is the base handler that processes all screen reader key bindings.
@@ -43,7 +49,7 @@ impl DefaultKeyHandler {
             keymap,
             last_key: None,
             last_key_time: Instant::now(),
-            repeat_timeout: Duration::from_millis(500),
+            repeat_timeout: DOUBLE_TAP_WINDOW,
         }
     }
 
@@ -97,6 +103,17 @@ impl DefaultKeyHandler {
         self.last_key = Some(key.to_vec());
         self.last_key_time = now;
 
+        // Alt+key may read a configured screen region
+        if let [0x1b, byte] = key {
+            let ch = *byte as char;
+            if byte.is_ascii_graphic() && state.has_region_key(ch) {
+                debug!("Reading region for key: {}", ch);
+                let screen = emulator.screen();
+                state.read_region(ch, screen)?;
+                return Ok(HandlerAction::Handled);
+            }
+        }
+
         // Convert key bytes to string for plugin lookup
         if let Ok(key_str) = String::from_utf8(key.to_vec()) {
             if state.has_plugin(&key_str) {
@@ -113,7 +130,34 @@ impl DefaultKeyHandler {
     /// Execute a screen reader action
     ///
     /// Each action performs navigation or mode switching.
-    fn execute_action(
+    /// Also used for braille display keys mapped to review actions.
+    ///
+    /// While selecting, movement of the review cursor is followed by the
+    /// size of the selection, in the same utterance.
+    pub fn execute_action(
+        &mut self,
+        action: &KeyAction,
+        state: &mut State,
+        emulator: &mut Emulator,
+    ) -> Result<HandlerAction> {
+        if !state.has_selection() {
+            return self.run_action(action, state, emulator);
+        }
+
+        // Speak the movement and the size as one utterance
+        state.hold_speech();
+        let before = state.review.pos;
+        let result = self.run_action(action, state, emulator);
+        let extent = if state.review.pos != before {
+            state.selection_extent()
+        } else {
+            None
+        };
+        state.release_speech(extent.as_deref())?;
+        result
+    }
+
+    fn run_action(
         &mut self,
         action: &KeyAction,
         state: &mut State,
@@ -144,19 +188,22 @@ impl DefaultKeyHandler {
             // Copy mode - push CopyHandler onto stack
             CopyMode => {
                 debug!("Entering copy mode");
+                let utterance = state.history.current().map(str::to_string);
                 state.speak("copy")?;
                 state
                     .handlers
-                    .push(Box::new(super::copy_handler::CopyHandler::new()));
+                    .push(Box::new(super::copy_handler::CopyHandler::with_utterance(
+                        utterance,
+                    )));
                 Ok(HandlerAction::Handled)
             }
 
             // Selection start/end - mark position for copying
             SelectionStart => {
                 if state.has_selection() {
+                    // Copied once a second press (switching modes) can't follow
                     debug!("Ending selection and copying");
-                    let screen = emulator.screen();
-                    state.copy_selection(screen)?;
+                    state.request_selection_copy();
                 } else {
                     debug!("Starting selection");
                     state.start_selection();
@@ -164,6 +211,11 @@ impl DefaultKeyHandler {
                 }
                 Ok(HandlerAction::Handled)
             }
+            ToggleSelectionMode => {
+                debug!("Toggling selection mode");
+                state.toggle_selection_mode()?;
+                Ok(HandlerAction::Handled)
+            }
 
             // Silence - cancel any pending speech
             Silence => {
@@ -192,6 +244,34 @@ impl DefaultKeyHandler {
                 state.next_line(screen)?;
                 Ok(HandlerAction::Handled)
             }
+
+            PrevBlock => {
+                debug!("Previous block");
+                let screen = emulator.screen();
+                state.move_to_block(screen, Direction::Backward, false)?;
+                Ok(HandlerAction::Handled)
+            }
+
+            NextBlock => {
+                debug!("Next block");
+                let screen = emulator.screen();
+                state.move_to_block(screen, Direction::Forward, false)?;
+                Ok(HandlerAction::Handled)
+            }
+
+            PrevIndentChange => {
+                debug!("Previous indentation change");
+                let screen = emulator.screen();
+                state.move_to_block(screen, Direction::Backward, true)?;
+                Ok(HandlerAction::Handled)
+            }
+
+            NextIndentChange => {
+                debug!("Next indentation change");
+                let screen = emulator.screen();
+                state.move_to_block(screen, Direction::Forward, true)?;
+                Ok(HandlerAction::Handled)
+            }
             PrevWord => {
                 debug!("Previous word");
                 let screen = emulator.screen();
@@ -240,6 +320,30 @@ impl DefaultKeyHandler {
                 state.next_char(screen)?;
                 Ok(HandlerAction::Handled)
             }
+            CharUp => {
+                debug!("Character above");
+                let screen = emulator.screen();
+                state.move_vertical(screen, Direction::Backward, false)?;
+                Ok(HandlerAction::Handled)
+            }
+            CharDown => {
+                debug!("Character below");
+                let screen = emulator.screen();
+                state.move_vertical(screen, Direction::Forward, false)?;
+                Ok(HandlerAction::Handled)
+            }
+            WordUp => {
+                debug!("Word above");
+                let screen = emulator.screen();
+                state.move_vertical(screen, Direction::Backward, true)?;
+                Ok(HandlerAction::Handled)
+            }
+            WordDown => {
+                debug!("Word below");
+                let screen = emulator.screen();
+                state.move_vertical(screen, Direction::Forward, true)?;
+                Ok(HandlerAction::Handled)
+            }
             TopOfScreen => {
                 debug!("Top of screen");
                 let screen = emulator.screen();
@@ -265,6 +369,127 @@ impl DefaultKeyHandler {
                 Ok(HandlerAction::Handled)
             }
 
+            // Speech history - review recently spoken utterances
+            HistoryPrev => {
+                debug!("Previous utterance");
+                state.history_prev()?;
+                Ok(HandlerAction::Handled)
+            }
+            HistoryNext => {
+                debug!("Next utterance");
+                state.history_next()?;
+                Ok(HandlerAction::Handled)
+            }
+            HistoryRepeat => {
+                debug!("Repeat last utterance");
+                state.repeat_last_utterance()?;
+                Ok(HandlerAction::Handled)
+            }
+
+            WhereAmI => {
+                debug!("Where am I");
+                let screen = emulator.screen();
+                state.where_am_i(screen)?;
+                Ok(HandlerAction::Handled)
+            }
+
+            WhereAmIDetails => {
+                debug!("Where am I details");
+                let screen = emulator.screen();
+                state.where_am_i_details(screen)?;
+                Ok(HandlerAction::Handled)
+            }
+
+            RouteCursor => {
+                debug!("Route cursor");
+                let screen = emulator.screen();
+                state.route_cursor(screen)?;
+                Ok(HandlerAction::Handled)
+            }
+
+            ReadLastOutput => {
+                debug!("Read last output");
+                let screen = emulator.screen();
+                state.read_last_output(screen)?;
+                Ok(HandlerAction::Handled)
+            }
+
+            SayAll => {
+                debug!("Say all");
+                let screen = emulator.screen();
+                state.start_say_all(screen, false)?;
+                Ok(HandlerAction::Handled)
+            }
+
+            SayAllFromTop => {
+                debug!("Say all from top");
+                let screen = emulator.screen();
+                state.start_say_all(screen, true)?;
+                Ok(HandlerAction::Handled)
+            }
+
+            TableMode => {
+                debug!("Entering table mode");
+                let screen = emulator.screen();
+                if let Some(table) = state.detect_table(screen)? {
+                    let row = state.review.pos.1 as usize;
+                    let column = table.column_at(state.review.pos.0 as usize);
+                    state.read_table_cell(screen, &table, row, column)?;
+                    state
+                        .handlers
+                        .push(Box::new(TableHandler::new(table, row, column)));
+                }
+                Ok(HandlerAction::Handled)
+            }
+
+            SetBookmark | JumpToBookmark | ReadBookmark => {
+                let (bookmark_action, prompt) = match action {
+                    SetBookmark => (BookmarkAction::Set, "set bookmark"),
+                    JumpToBookmark => (BookmarkAction::Jump, "go to bookmark"),
+                    _ => (BookmarkAction::Read, "read bookmark"),
+                };
+                debug!("Bookmark: {}", prompt);
+                state.speak(prompt)?;
+                state
+                    .handlers
+                    .push(Box::new(BookmarkHandler::new(bookmark_action)));
+                Ok(HandlerAction::Handled)
+            }
+
+            FindText | FindRegex => {
+                let is_regex = *action == FindRegex;
+                debug!("Find (regex: {})", is_regex);
+                state.speak(if is_regex { "find regex" } else { "find" })?;
+                state
+                    .handlers
+                    .push(Box::new(super::buffer_handler::BufferHandler::with_screen(
+                        Box::new(move |input: String, state: &mut State, screen: &Screen| {
+                            state.start_search(&input, is_regex, screen)
+                        }),
+                    )));
+                Ok(HandlerAction::Handled)
+            }
+
+            FindNext => {
+                debug!("Find next");
+                let screen = emulator.screen();
+                state.find(screen, Direction::Forward)?;
+                Ok(HandlerAction::Handled)
+            }
+
+            FindPrevious => {
+                debug!("Find previous");
+                let screen = emulator.screen();
+                state.find(screen, Direction::Backward)?;
+                Ok(HandlerAction::Handled)
+            }
+
+            CycleSymbolLevel => {
+                debug!("Cycle symbol level");
+                state.cycle_symbol_level()?;
+                Ok(HandlerAction::Handled)
+            }
+
             // Arrow keys - pass through but schedule delayed speech
             ArrowUp => {
                 debug!("Arrow up");
@@ -60,6 +60,38 @@ impl HandlerStack {
         self.handlers.pop()
     }
 
+    /// Put back a handler that was popped to process a key
+    ///
+    /// `depth` is the stack size right after it was popped. Handlers it
+    /// pushed while processing (e.g. a text prompt from the config menu)
+    /// stay above it, so they get the following keys.
+    fn restore(&mut self, depth: usize, handler: Box<dyn KeyHandler>) {
+        let index = depth.min(self.handlers.len());
+        self.handlers.insert(index, handler);
+    }
+
+    /// Process a key with the top handler of the state's stack
+    ///
+    /// The handler is taken off the stack while it runs, since it gets the
+    /// state to work with, and put back afterwards unless it removed
+    /// itself. Returns Passthrough if no handler is active.
+    pub fn dispatch(
+        state: &mut State,
+        key: &[u8],
+        emulator: &mut Emulator,
+    ) -> Result<HandlerAction> {
+        let mut handler = match state.handlers.pop() {
+            Some(handler) => handler,
+            None => return Ok(HandlerAction::Passthrough),
+        };
+        let depth = state.handlers.len();
+        let action = handler.process_with_context(key, state, emulator)?;
+        if action != HandlerAction::Remove {
+            state.handlers.restore(depth, handler);
+        }
+        Ok(action)
+    }
+
     /// Process a key with the top handler (with state and emulator access)
     pub fn process_with_context(
         &mut self,
@@ -107,3 +139,86 @@ impl Default for HandlerStack {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::testing::test_state;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// Config-menu stand-in: records keys and opens a prompt on "D"
+    struct MenuHandler {
+        log: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl KeyHandler for MenuHandler {
+        fn process(&mut self, _key: &[u8]) -> Result<HandlerAction> {
+            Ok(HandlerAction::Handled)
+        }
+
+        fn process_with_context(
+            &mut self,
+            key: &[u8],
+            state: &mut State,
+            _emulator: &mut Emulator,
+        ) -> Result<HandlerAction> {
+            self.log
+                .borrow_mut()
+                .push(format!("menu {}", String::from_utf8_lossy(key)));
+            if key == b"D" {
+                state.handlers.push(Box::new(PromptHandler {
+                    log: self.log.clone(),
+                    buffer: String::new(),
+                }));
+            }
+            Ok(HandlerAction::Handled)
+        }
+    }
+
+    /// Text prompt stand-in: collects keys until Enter
+    struct PromptHandler {
+        log: Rc<RefCell<Vec<String>>>,
+        buffer: String,
+    }
+
+    impl KeyHandler for PromptHandler {
+        fn process(&mut self, key: &[u8]) -> Result<HandlerAction> {
+            if key == b"\r" {
+                self.log
+                    .borrow_mut()
+                    .push(format!("accepted {}", self.buffer));
+                Ok(HandlerAction::Remove)
+            } else {
+                self.buffer.push_str(&String::from_utf8_lossy(key));
+                Ok(HandlerAction::Handled)
+            }
+        }
+    }
+
+    #[test]
+    fn test_prompt_pushed_by_menu_gets_the_text() {
+        let (mut state, _) = test_state(20, 4);
+        let mut emulator = Emulator::new(20, 4);
+        let log = Rc::new(RefCell::new(Vec::new()));
+        state
+            .handlers
+            .push(Box::new(MenuHandler { log: log.clone() }));
+
+        for key in [&b"D"[..], b"e", b"c", b"h", b"o", b"\r", b"l"] {
+            HandlerStack::dispatch(&mut state, key, &mut emulator).unwrap();
+        }
+
+        // The typed text goes to the prompt, then the menu gets keys again
+        assert_eq!(*log.borrow(), vec!["menu D", "accepted echo", "menu l"]);
+        assert_eq!(state.handlers.len(), 1);
+    }
+
+    #[test]
+    fn test_dispatch_without_handlers_passes_through() {
+        let (mut state, _) = test_state(20, 4);
+        let mut emulator = Emulator::new(20, 4);
+        let action = HandlerStack::dispatch(&mut state, b"x", &mut emulator).unwrap();
+        assert_eq!(action, HandlerAction::Passthrough);
+    }
+}
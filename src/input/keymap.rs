@@ -14,6 +14,10 @@ pub enum KeyAction {
     PrevLine,
     CurrentLine,
     NextLine,
+    PrevBlock,
+    NextBlock,
+    PrevIndentChange,
+    NextIndentChange,
 
     // Word navigation
     PrevWord,
@@ -26,6 +30,10 @@ pub enum KeyAction {
     CurrentChar,
     NextChar,
     SayCharPhonetic,
+    CharUp,
+    CharDown,
+    WordUp,
+    WordDown,
 
     // Screen navigation
     TopOfScreen,
@@ -43,15 +51,56 @@ pub enum KeyAction {
     Backspace,
     Delete,
 
+    // Speech history
+    HistoryPrev,
+    HistoryNext,
+    HistoryRepeat,
+
+    // Position reporting
+    WhereAmI,
+    WhereAmIDetails,
+
+    // Cursor routing
+    RouteCursor,
+
+    // Command output
+    ReadLastOutput,
+
+    // Continuous reading
+    SayAll,
+    SayAllFromTop,
+
+    // Table navigation
+    TableMode,
+
+    // Bookmarks
+    SetBookmark,
+    JumpToBookmark,
+    ReadBookmark,
+
+    // Search
+    FindText,
+    FindRegex,
+    FindNext,
+    FindPrevious,
+
+    // Speech settings
+    CycleSymbolLevel,
+
     // Modes
     Config,
     QuietMode,
     SelectionStart,
+    ToggleSelectionMode,
     CopyMode,
     Silence,
 }
 
 /// Create the default keymap
+///
+/// Many of these Alt keys are also readline and zle bindings, which the
+/// user reaches by pressing Escape and then the key. Word movement
+/// (alt+b and alt+f) is always left to the shell.
 pub fn create_default_keymap() -> HashMap<KeySequence, KeyAction> {
     let mut map = HashMap::new();
 
@@ -60,6 +109,12 @@ pub fn create_default_keymap() -> HashMap<KeySequence, KeyAction> {
     map.insert(b"\x1bi".to_vec(), KeyAction::CurrentLine);
     map.insert(b"\x1bo".to_vec(), KeyAction::NextLine);
 
+    // Block navigation (alt+{/} by blank lines, alt+(/) by indentation)
+    map.insert(b"\x1b{".to_vec(), KeyAction::PrevBlock);
+    map.insert(b"\x1b}".to_vec(), KeyAction::NextBlock);
+    map.insert(b"\x1b(".to_vec(), KeyAction::PrevIndentChange);
+    map.insert(b"\x1b)".to_vec(), KeyAction::NextIndentChange);
+
     // Word navigation (alt+j/k/l)
     map.insert(b"\x1bj".to_vec(), KeyAction::PrevWord);
     map.insert(b"\x1bk".to_vec(), KeyAction::CurrentWord);
@@ -70,6 +125,12 @@ pub fn create_default_keymap() -> HashMap<KeySequence, KeyAction> {
     map.insert(b"\x1b,".to_vec(), KeyAction::CurrentChar);
     map.insert(b"\x1b.".to_vec(), KeyAction::NextChar);
 
+    // Vertical movement in the same column (alt+y/n by character, alt+Y/N by word)
+    map.insert(b"\x1by".to_vec(), KeyAction::CharUp);
+    map.insert(b"\x1bn".to_vec(), KeyAction::CharDown);
+    map.insert(b"\x1bY".to_vec(), KeyAction::WordUp);
+    map.insert(b"\x1bN".to_vec(), KeyAction::WordDown);
+
     // Screen edges (alt+U/O/M/>)
     map.insert(b"\x1bU".to_vec(), KeyAction::TopOfScreen);
     map.insert(b"\x1bO".to_vec(), KeyAction::BottomOfScreen);
@@ -92,6 +153,40 @@ pub fn create_default_keymap() -> HashMap<KeySequence, KeyAction> {
     map.insert(b"\x7f".to_vec(), KeyAction::Backspace);
     map.insert(b"\x1b[3~".to_vec(), KeyAction::Delete);
 
+    // Speech history (alt+h/H/;)
+    map.insert(b"\x1bh".to_vec(), KeyAction::HistoryPrev);
+    map.insert(b"\x1bH".to_vec(), KeyAction::HistoryNext);
+    map.insert(b"\x1b;".to_vec(), KeyAction::HistoryRepeat);
+
+    // Where am I (alt+w)
+    map.insert(b"\x1bw".to_vec(), KeyAction::WhereAmI);
+
+    // Route the terminal cursor to the review cursor (alt+\)
+    map.insert(b"\x1b\\".to_vec(), KeyAction::RouteCursor);
+
+    // Last command's output (alt+e)
+    map.insert(b"\x1be".to_vec(), KeyAction::ReadLastOutput);
+
+    // Say all (alt+a)
+    map.insert(b"\x1ba".to_vec(), KeyAction::SayAll);
+
+    // Table mode (alt+t)
+    map.insert(b"\x1bt".to_vec(), KeyAction::TableMode);
+
+    // Bookmarks (alt+' to set, alt+g to jump, alt+G to read; then a letter)
+    map.insert(b"\x1b'".to_vec(), KeyAction::SetBookmark);
+    map.insert(b"\x1bg".to_vec(), KeyAction::JumpToBookmark);
+    map.insert(b"\x1bG".to_vec(), KeyAction::ReadBookmark);
+
+    // Search (alt+/ and alt+? to search, alt+z/Z for next/previous)
+    map.insert(b"\x1b/".to_vec(), KeyAction::FindText);
+    map.insert(b"\x1b?".to_vec(), KeyAction::FindRegex);
+    map.insert(b"\x1bz".to_vec(), KeyAction::FindNext);
+    map.insert(b"\x1bZ".to_vec(), KeyAction::FindPrevious);
+
+    // Symbol level (alt+p)
+    map.insert(b"\x1bp".to_vec(), KeyAction::CycleSymbolLevel);
+
     // Modes
     map.insert(b"\x1bc".to_vec(), KeyAction::Config);
     map.insert(b"\x1bq".to_vec(), KeyAction::QuietMode);
@@ -104,6 +199,12 @@ pub fn create_default_keymap() -> HashMap<KeySequence, KeyAction> {
     map.insert(b"\x1bk\x1bk".to_vec(), KeyAction::SpellWord);
     // alt+comma twice = phonetic character
     map.insert(b"\x1b,\x1b,".to_vec(), KeyAction::SayCharPhonetic);
+    // alt+a twice = say all from the top of the scrollback
+    map.insert(b"\x1ba\x1ba".to_vec(), KeyAction::SayAllFromTop);
+    // alt+w twice = window title and working directory
+    map.insert(b"\x1bw\x1bw".to_vec(), KeyAction::WhereAmIDetails);
+    // alt+r twice = switch between linear and block selection
+    map.insert(b"\x1br\x1br".to_vec(), KeyAction::ToggleSelectionMode);
 
     map
 }
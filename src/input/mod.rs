@@ -3,12 +3,15 @@
 //! The input system uses a stack-based handler architecture where handlers
 //! can be pushed/popped to create modal interfaces (config menu, copy mode, etc.)
 
+pub mod bookmark_handler;
 pub mod buffer_handler;
 pub mod config_handler;
 pub mod copy_handler;
 pub mod default_handler;
 pub mod handler;
 pub mod keymap;
+pub mod routing;
+pub mod table_handler;
 
 pub use default_handler::DefaultKeyHandler;
 pub use handler::{HandlerAction, HandlerStack, KeyHandler};
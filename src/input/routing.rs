@@ -0,0 +1,46 @@
+//! Cursor routing
+//!
+//! Builds the arrow key presses that move the application's cursor to the
+//! review cursor, as if the user had typed them.
+
+/// Arrow key sequence in normal or application cursor mode
+///
+/// Programs that enable application cursor keys (DECCKM) expect
+/// `ESC O x` rather than `ESC [ x`.
+pub fn arrow(key: u8, app_cursor: bool) -> [u8; 3] {
+    if app_cursor {
+        [0x1b, b'O', key]
+    } else {
+        [0x1b, b'[', key]
+    }
+}
+
+/// Key presses moving the cursor by rows and then by characters
+///
+/// Positive values move down and right.
+pub fn route_keys(rows: i32, chars: i32, app_cursor: bool) -> Vec<u8> {
+    let vertical = if rows < 0 { b'A' } else { b'B' };
+    let horizontal = if chars < 0 { b'D' } else { b'C' };
+
+    let mut keys = Vec::new();
+    for _ in 0..rows.unsigned_abs() {
+        keys.extend_from_slice(&arrow(vertical, app_cursor));
+    }
+    for _ in 0..chars.unsigned_abs() {
+        keys.extend_from_slice(&arrow(horizontal, app_cursor));
+    }
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_keys() {
+        assert_eq!(route_keys(0, 2, false), b"\x1b[C\x1b[C");
+        assert_eq!(route_keys(0, -1, false), b"\x1b[D");
+        assert_eq!(route_keys(-1, 1, true), b"\x1bOA\x1bOC");
+        assert!(route_keys(0, 0, false).is_empty());
+    }
+}
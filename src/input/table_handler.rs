@@ -0,0 +1,106 @@
+//! Table mode handler
+//!
+//! Modal handler for moving through tabular output by cell (alt+t).
+
+use super::{HandlerAction, KeyHandler};
+use crate::review::table::Table;
+use crate::state::State;
+use crate::terminal::Emulator;
+use crate::Result;
+use log::debug;
+
+/// Table mode key handler
+///
+/// While active, the arrow keys move the review cursor by cell:
+/// - Left/Right: previous/next column
+/// - Up/Down: previous/next row
+/// - Space: read the current cell again
+/// - Escape or alt+t: leave table mode
+/// - Other: leave table mode
+pub struct TableHandler {
+    table: Table,
+
+    /// Current row (a screen line) and column
+    row: usize,
+    column: usize,
+}
+
+impl TableHandler {
+    /// Start table navigation at a cell
+    pub fn new(table: Table, row: usize, column: usize) -> Self {
+        Self { table, row, column }
+    }
+
+    /// Process table mode keys
+    pub fn process_with_state(
+        &mut self,
+        key: &[u8],
+        state: &mut State,
+        emulator: &mut Emulator,
+    ) -> Result<HandlerAction> {
+        let screen = emulator.screen();
+        let edge = match key {
+            b"\x1b[D" | b"\x1bOD" => {
+                if self.column == 0 {
+                    Some("start of row")
+                } else {
+                    self.column -= 1;
+                    None
+                }
+            }
+            b"\x1b[C" | b"\x1bOC" => {
+                if self.column + 1 >= self.table.columns() {
+                    Some("end of row")
+                } else {
+                    self.column += 1;
+                    None
+                }
+            }
+            b"\x1b[A" | b"\x1bOA" => {
+                if self.row <= self.table.rows.start {
+                    Some("top")
+                } else {
+                    self.row -= 1;
+                    None
+                }
+            }
+            b"\x1b[B" | b"\x1bOB" => {
+                if self.row + 1 >= self.table.rows.end {
+                    Some("bottom")
+                } else {
+                    self.row += 1;
+                    None
+                }
+            }
+            b" " => None,
+            _ => {
+                debug!("Table mode: leaving");
+                state.speak("table off")?;
+                return Ok(HandlerAction::Remove);
+            }
+        };
+
+        if let Some(edge) = edge {
+            state.speak(edge)?;
+        }
+        debug!("Table mode: row {} column {}", self.row, self.column);
+        state.read_table_cell(screen, &self.table, self.row, self.column)?;
+        Ok(HandlerAction::Handled)
+    }
+}
+
+impl KeyHandler for TableHandler {
+    fn process(&mut self, _key: &[u8]) -> Result<HandlerAction> {
+        // This shouldn't be called directly - use process_with_context instead
+        Ok(HandlerAction::Handled)
+    }
+
+    fn process_with_context(
+        &mut self,
+        key: &[u8],
+        state: &mut State,
+        emulator: &mut Emulator,
+    ) -> Result<HandlerAction> {
+        self.process_with_state(key, state, emulator)
+    }
+}
@@ -3,6 +3,7 @@
 //! A console-based screen reader for *nix systems (macOS, Linux, FreeBSD).
 //! Provides text-to-speech feedback for terminal applications.
 
+pub mod braille;
 pub mod clipboard;
 pub mod error;
 pub mod input;
@@ -13,6 +14,7 @@ pub mod speech;
 pub mod state;
 pub mod symbols;
 pub mod terminal;
+pub mod unicode_names;
 
 pub use error::{Result, TdsrError};
 
@@ -1,9 +1,10 @@
 //! TDSR main entry point
 //!
-//! The screen reader's main loop monitors three sources:
+//! The screen reader's main loop monitors these sources:
 //! 1. stdin (user keyboard input) - passed to shell
 //! 2. PTY output (shell output) - parsed and spoken
 //! 3. Signals (SIGWINCH for resize) - updates screen size
+//! 4. Braille display keys (when a display is connected)
 
 use log::{debug, error, info};
 use mio::{Events, Interest, Poll, Token};
@@ -13,7 +14,8 @@ use std::io::{self, Read, Write};
 use std::os::unix::io::{AsRawFd, RawFd};
 use std::process;
 use std::sync::atomic::{AtomicBool, Ordering};
-use tdsr::input::{create_default_keymap, DefaultKeyHandler, HandlerAction};
+use std::time::Instant;
+use tdsr::input::{create_default_keymap, DefaultKeyHandler, HandlerAction, HandlerStack};
 use tdsr::platform::is_wsl;
 use tdsr::state::State;
 use tdsr::terminal::{get_terminal_size, restore_termios, set_raw_mode, Emulator, Pty};
@@ -23,6 +25,8 @@ use tdsr::Result;
 const STDIN: Token = Token(0);
 /// Token for PTY in mio poll
 const PTY: Token = Token(1);
+/// Token for braille display connection in mio poll
+const BRAILLE: Token = Token(2);
 
 /// Global flag set by SIGWINCH handler
 static RESIZE_PENDING: AtomicBool = AtomicBool::new(false);
@@ -122,11 +126,15 @@ fn run() -> Result<()> {
     // Create PTY and spawn shell
     // This is the core of the screen reader - we sit between user and shell
     let mut pty = Pty::new(program, rows, cols)?;
+    state.shell_pid = pty.child_pid();
     info!("PTY created, shell spawned");
 
     // Create terminal emulator
     // This maintains the screen buffer for review cursor navigation
     let mut emulator = Emulator::new(cols, rows);
+    emulator
+        .screen_mut()
+        .set_scrollback_limit(state.config.scrollback_lines());
 
     // Create default key handler for screen reader commands
     // This processes Alt+key combinations for navigation
@@ -166,6 +174,13 @@ fn run() -> Result<()> {
         poll.registry()
             .register(&mut pty_source, PTY, Interest::READABLE)?;
 
+        // Register braille display for key presses
+        if let Some(braille_fd) = state.braille_fd() {
+            let mut braille_source = mio::unix::SourceFd(&braille_fd);
+            poll.registry()
+                .register(&mut braille_source, BRAILLE, Interest::READABLE)?;
+        }
+
         Some((poll, Events::with_capacity(128)))
     } else {
         debug!("Using select() for event loop (WSL mode)");
@@ -179,8 +194,8 @@ fn run() -> Result<()> {
 
     println!("TDSR {} ready - All phases complete!", tdsr::VERSION);
     println!("Configuration loaded: {}", state.config.path().display());
-    println!("  Process symbols: {}", state.config.process_symbols());
-    println!("  Key echo: {}", state.config.key_echo());
+    println!("  Symbol level: {}", state.config.symbol_level().name());
+    println!("  Key echo: {}", state.config.key_echo().name());
     println!("  Cursor tracking: {}", state.config.cursor_tracking());
     println!("  Symbols loaded: {}", state.config.symbols.len());
     println!("Speech synthesizer initialized");
@@ -204,12 +219,51 @@ fn run() -> Result<()> {
             state.resize(new_cols, new_rows);
         }
 
+        // Send keys queued by screen reader commands, such as cursor routing
+        let input = state.take_pending_input();
+        if !input.is_empty() {
+            pty.write(&input)?;
+        }
+
         // Run any scheduled functions that are ready
         let screen = emulator.screen();
         if let Err(e) = state.run_scheduled(screen) {
             error!("Error running scheduled function: {}", e);
         }
 
+        // Copy a selection once a second alt+r can no longer follow
+        if let Err(e) = state.check_selection_copy(screen) {
+            error!("Error copying selection: {}", e);
+        }
+
+        // Read the next line of a say-all once the last is spoken
+        if let Err(e) = state.continue_say_all(screen) {
+            error!("Error continuing say all: {}", e);
+        }
+
+        // Announce watched screen regions that have changed
+        if let Err(e) = state.check_regions(screen) {
+            error!("Error checking regions: {}", e);
+        }
+
+        // Speak output whose coalescing window has passed
+        if let Err(e) = state.speak_held_output() {
+            error!("Error speaking output: {}", e);
+        }
+
+        // Speak the selected item of a full-screen menu if it moved
+        if let Err(e) = state.check_highlight(screen) {
+            error!("Error tracking highlight: {}", e);
+        }
+
+        // Announce the size of any output flood that has settled
+        if let Err(e) = state.check_flood_settled() {
+            error!("Error announcing output flood: {}", e);
+        }
+
+        // Keep the braille display on the review cursor's line
+        state.refresh_braille(screen);
+
         if use_select {
             // WSL mode: Use select() for I/O monitoring
             use nix::sys::select::{select, FdSet};
@@ -219,11 +273,17 @@ fn run() -> Result<()> {
             // Create borrowed FDs for select (must be created each iteration)
             let stdin_borrowed = unsafe { BorrowedFd::borrow_raw(stdin_fd) };
             let pty_borrowed = unsafe { BorrowedFd::borrow_raw(pty_fd) };
+            let braille_borrowed = state
+                .braille_fd()
+                .map(|fd| unsafe { BorrowedFd::borrow_raw(fd) });
 
             // Rebuild FdSet each iteration (select() modifies it)
             let mut read_fds = FdSet::new();
             read_fds.insert(stdin_borrowed);
             read_fds.insert(pty_borrowed);
+            if let Some(fd) = braille_borrowed {
+                read_fds.insert(fd);
+            }
 
             // Calculate timeout based on scheduled functions or use default
             let mut timeout = if let Some(delay) = state.time_until_next_scheduled() {
@@ -255,6 +315,13 @@ fn run() -> Result<()> {
                             return Err(e);
                         }
                     }
+                    if braille_borrowed.is_some_and(|fd| read_fds.contains(fd)) {
+                        if let Err(e) =
+                            handle_braille_input(&mut state, &mut emulator, &mut default_handler)
+                        {
+                            error!("Braille error: {}", e);
+                        }
+                    }
                 }
                 Err(nix::errno::Errno::EBADF) => {
                     error!("select() failed: Bad file descriptor");
@@ -298,6 +365,13 @@ fn run() -> Result<()> {
                             return Err(e);
                         }
                     }
+                    BRAILLE => {
+                        if let Err(e) =
+                            handle_braille_input(&mut state, &mut emulator, &mut default_handler)
+                        {
+                            error!("Braille error: {}", e);
+                        }
+                    }
                     _ => {}
                 }
             }
@@ -324,41 +398,19 @@ fn handle_stdin(
 
     let input = &buf[..n];
 
-    // Cancel any pending speech and clear delayed functions
-    state.cancel_speech()?;
-    state.clear_delayed_functions();
+    // Stop say-all, pending speech and delayed functions
+    state.interrupt()?;
 
     // Process through handler stack if there are modal handlers active
     // (e.g., config menu, copy mode, buffer input)
     if !state.handlers.is_empty() {
-        // Temporarily pop the handler to avoid borrow checker issues
-        if let Some(mut handler) = state.handlers.pop() {
-            let action = handler.process_with_context(input, state, emulator)?;
-
-            match action {
-                HandlerAction::Passthrough => {
-                    // Push handler back (it wants to stay active)
-                    state.handlers.push(handler);
-                    // Track the last key for key_echo feature
-                    if input.len() == 1 {
-                        let ch = input[0] as char;
-                        if ch.is_ascii_graphic() || ch == ' ' {
-                            state.last_key = Some(ch);
-                        }
-                    } else {
-                        state.last_key = None;
-                    }
-                    // Pass key through to shell
-                    pty.write(input)?;
-                }
-                HandlerAction::Remove => {
-                    // Handler removed itself, don't push back
-                }
-                HandlerAction::Handled => {
-                    // Push handler back (it wants to stay active)
-                    state.handlers.push(handler);
-                }
-            }
+        let action = HandlerStack::dispatch(state, input, emulator)?;
+        if action == HandlerAction::Passthrough {
+            // The handler stays active but lets the key through to the shell
+            // Track the key for key echo
+            state.key_typed(input, pty.input_hidden())?;
+            emulator.screen_mut().note_typing();
+            pty.write(input)?;
         }
         return Ok(());
     }
@@ -369,16 +421,9 @@ fn handle_stdin(
     match action {
         HandlerAction::Passthrough => {
             // Not a screen reader command - pass to shell
-            // Track the last key for key_echo feature
-            if input.len() == 1 {
-                let ch = input[0] as char;
-                if ch.is_ascii_graphic() || ch == ' ' {
-                    state.last_key = Some(ch);
-                }
-            } else {
-                // Multi-byte input (escape sequence, etc.) - clear last key
-                state.last_key = None;
-            }
+            // Track the key for key echo
+            state.key_typed(input, pty.input_hidden())?;
+            emulator.screen_mut().note_typing();
             pty.write(input)?;
         }
         HandlerAction::Handled => {
@@ -392,6 +437,21 @@ fn handle_stdin(
     Ok(())
 }
 
+/// Handle key presses on the braille display
+///
+/// Display keys drive the review cursor just like the keyboard bindings,
+/// so the display and speech stay on the same position.
+fn handle_braille_input(
+    state: &mut State,
+    emulator: &mut Emulator,
+    default_handler: &mut DefaultKeyHandler,
+) -> Result<()> {
+    for command in state.read_braille_commands() {
+        state.braille_command(command, emulator, default_handler)?;
+    }
+    Ok(())
+}
+
 /// Handle output from PTY
 ///
 /// This is the core screen reader function - we parse terminal output,
@@ -417,8 +477,6 @@ fn handle_pty_output(pty: &mut Pty, emulator: &mut Emulator, state: &mut State)
     // If quiet mode or temp_silence is active, don't add to speech
     if !state.quiet && !state.temp_silence {
         let line_pause = state.config.line_pause();
-        let key_echo = state.config.key_echo();
-        let last_key = state.last_key;
 
         emulator.process_with_speech(
             output,
@@ -428,43 +486,41 @@ fn handle_pty_output(pty: &mut Pty, emulator: &mut Emulator, state: &mut State)
         )?;
 
         // Key echo: if output is just the last typed character being echoed,
-        // speak it as a character (phonetically) instead of normal speech
-        if key_echo {
-            if let Some(typed_key) = last_key {
-                // Check if output is just the single character we typed
-                // (terminals typically echo the character immediately)
-                if output.len() == 1 && output[0] as char == typed_key {
-                    // Speak the character and skip normal speech buffer
-                    state.speak_char(typed_key)?;
-                    state.speech_buffer.flush(); // Discard the character from buffer
-                    state.last_key = None;
-                    return Ok(());
-                }
+        // speak it (and any word it completes) instead of normal speech.
+        // Otherwise the echo window has passed and output is spoken as usual.
+        if let Some(echo) = state.key_echo.echoed(output) {
+            if let Some(ch) = echo.character {
+                state.speak_char(ch)?;
+            }
+            if let Some(word) = echo.word {
+                state.speak(&word)?;
             }
+            // Discard the character, keeping any held output
+            state.speech_buffer.pop();
+            return Ok(());
         }
 
-        // Clear last_key after processing (echo window has passed)
-        state.last_key = None;
-
         // If line_pause is enabled, speak each line separately
         if line_pause && state.speech_buffer.has_pending_lines() {
             for line in state.speech_buffer.drain_lines() {
                 if !line.is_empty() {
-                    state.speak(&line)?;
+                    state.speak_output(&line)?;
                 }
             }
         }
 
-        // Flush any remaining buffer content to TTS
-        if !state.speech_buffer.is_empty() {
-            let text = state.speech_buffer.flush();
-            state.speak(&text)?;
-        }
+        // Hold any partial line briefly so output split across reads
+        // is spoken together; it is flushed once the window passes
+        state.speech_buffer.mark_output(Instant::now());
+        state.speak_held_output()?;
     } else {
         // Quiet mode - just update screen buffer without speech
         emulator.process(output)?;
     }
 
+    // Look for a moved highlight once this burst of output settles
+    state.output_received();
+
     // Adjust review cursor for any scrolling that occurred
     let scroll_offset = emulator.screen_mut().take_scroll_offset();
     if scroll_offset != 0 {
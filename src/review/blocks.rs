@@ -0,0 +1,215 @@
+//! Block navigation
+//!
+//! Blocks are runs of non-blank lines separated by blank lines, such as
+//! paragraphs in a man page or groups of command output. Indentation
+//! runs are lines sharing the same indentation, with blank lines between
+//! them ignored. Both let the review cursor skim a screen quickly.
+
+use super::indentation::indentation_width;
+use super::search::Direction;
+
+/// A block found by navigation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Block {
+    /// First line of the block
+    pub start: usize,
+
+    /// Number of lines in the block, not counting blank lines
+    pub lines: usize,
+}
+
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+/// Find the previous or next block of text from line `from`
+///
+/// Going backward finds the block before the one containing `from`,
+/// not the start of the current block.
+pub fn find_block(lines: &[String], from: usize, direction: Direction) -> Option<Block> {
+    let count = lines.len();
+    let blank = |i: usize| is_blank(&lines[i]);
+
+    let start = match direction {
+        Direction::Forward => {
+            let mut i = from;
+            while i < count && !blank(i) {
+                i += 1;
+            }
+            while i < count && blank(i) {
+                i += 1;
+            }
+            if i >= count {
+                return None;
+            }
+            i
+        }
+        Direction::Backward => {
+            let mut i = from.min(count.checked_sub(1)?);
+            if !blank(i) {
+                while i > 0 && !blank(i - 1) {
+                    i -= 1;
+                }
+            }
+            loop {
+                if i == 0 {
+                    return None;
+                }
+                i -= 1;
+                if !blank(i) {
+                    break;
+                }
+            }
+            while i > 0 && !blank(i - 1) {
+                i -= 1;
+            }
+            i
+        }
+    };
+
+    let len = lines[start..].iter().take_while(|l| !is_blank(l)).count();
+    Some(Block { start, lines: len })
+}
+
+/// Find the previous or next change in indentation from line `from`
+///
+/// The result is the first line of the run with the new indentation.
+/// From a blank line, any non-blank line counts as a change.
+pub fn find_indent_change(lines: &[String], from: usize, direction: Direction) -> Option<Block> {
+    let count = lines.len();
+    let from = from.min(count.checked_sub(1)?);
+    let current = indentation_width(&lines[from]);
+    let differs = |i: usize| match indentation_width(&lines[i]) {
+        Some(width) => current != Some(width),
+        None => false,
+    };
+
+    let found = match direction {
+        Direction::Forward => (from + 1..count).find(|&i| differs(i))?,
+        Direction::Backward => {
+            let start = match current {
+                Some(_) => run_start(lines, from),
+                None => from,
+            };
+            run_start(lines, (0..start).rev().find(|&i| differs(i))?)
+        }
+    };
+
+    Some(Block {
+        start: found,
+        lines: run_length(lines, found),
+    })
+}
+
+/// First line of the indentation run containing non-blank line `at`
+fn run_start(lines: &[String], at: usize) -> usize {
+    let width = indentation_width(&lines[at]);
+    let mut start = at;
+    for i in (0..at).rev() {
+        match indentation_width(&lines[i]) {
+            None => continue,
+            w if w == width => start = i,
+            _ => break,
+        }
+    }
+    start
+}
+
+/// Number of non-blank lines in the indentation run starting at `start`
+fn run_length(lines: &[String], start: usize) -> usize {
+    let width = indentation_width(&lines[start]);
+    lines[start..]
+        .iter()
+        .map(|line| indentation_width(line))
+        .filter(|w| w.is_some())
+        .take_while(|&w| w == width)
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &[&str]) -> Vec<String> {
+        text.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn block(start: usize, lines: usize) -> Option<Block> {
+        Some(Block { start, lines })
+    }
+
+    #[test]
+    fn test_next_block() {
+        let text = lines(&[
+            "NAME",
+            "  ls",
+            "",
+            "",
+            "SYNOPSIS",
+            "  ls [OPTION]",
+            "  more",
+            "",
+        ]);
+        assert_eq!(find_block(&text, 0, Direction::Forward), block(4, 3));
+        assert_eq!(find_block(&text, 2, Direction::Forward), block(4, 3));
+        assert_eq!(find_block(&text, 5, Direction::Forward), None);
+    }
+
+    #[test]
+    fn test_previous_block() {
+        let text = lines(&["one", "two", "", "three", "four", "", "five"]);
+        // From inside a block, skip to the block before it
+        assert_eq!(find_block(&text, 4, Direction::Backward), block(0, 2));
+        assert_eq!(find_block(&text, 6, Direction::Backward), block(3, 2));
+        assert_eq!(find_block(&text, 5, Direction::Backward), block(3, 2));
+        assert_eq!(find_block(&text, 1, Direction::Backward), None);
+    }
+
+    #[test]
+    fn test_indent_change() {
+        let text = lines(&[
+            "def f():",
+            "    a = 1",
+            "",
+            "    b = 2",
+            "        c",
+            "    d",
+            "print()",
+        ]);
+        assert_eq!(
+            find_indent_change(&text, 0, Direction::Forward),
+            block(1, 2)
+        );
+        assert_eq!(
+            find_indent_change(&text, 1, Direction::Forward),
+            block(4, 1)
+        );
+        assert_eq!(find_indent_change(&text, 6, Direction::Forward), None);
+
+        assert_eq!(
+            find_indent_change(&text, 3, Direction::Backward),
+            block(0, 1)
+        );
+        assert_eq!(
+            find_indent_change(&text, 5, Direction::Backward),
+            block(4, 1)
+        );
+        assert_eq!(
+            find_indent_change(&text, 6, Direction::Backward),
+            block(5, 1)
+        );
+    }
+
+    #[test]
+    fn test_indent_change_from_blank() {
+        let text = lines(&["a", "", "  b"]);
+        assert_eq!(
+            find_indent_change(&text, 1, Direction::Forward),
+            block(2, 1)
+        );
+        assert_eq!(
+            find_indent_change(&text, 1, Direction::Backward),
+            block(0, 1)
+        );
+    }
+}
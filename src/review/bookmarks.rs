@@ -0,0 +1,101 @@
+//! Named review bookmarks
+//!
+//! A bookmark remembers a review cursor position under a letter. Bookmarks
+//! follow their content as the screen scrolls, and become stale once that
+//! content has scrolled off the screen.
+
+use std::collections::HashMap;
+
+/// A remembered review position
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bookmark {
+    /// Position (x, y) on screen
+    At(u16, u16),
+
+    /// The bookmarked content has scrolled off the screen
+    Stale,
+}
+
+/// Bookmarks by name
+#[derive(Default)]
+pub struct Bookmarks {
+    marks: HashMap<char, Bookmark>,
+}
+
+impl Bookmarks {
+    /// Create an empty set of bookmarks
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check if a character can name a bookmark
+    pub fn is_valid_name(name: char) -> bool {
+        name.is_ascii_alphanumeric()
+    }
+
+    /// Set a bookmark, replacing any with the same name
+    pub fn set(&mut self, name: char, pos: (u16, u16)) {
+        self.marks.insert(name, Bookmark::At(pos.0, pos.1));
+    }
+
+    /// Look up a bookmark
+    pub fn get(&self, name: char) -> Option<Bookmark> {
+        self.marks.get(&name).copied()
+    }
+
+    /// Move bookmarks with scrolled content
+    ///
+    /// scroll_offset: positive = scrolled up, negative = scrolled down,
+    /// as for the review cursor. Bookmarks pushed past either edge of
+    /// the screen become stale.
+    pub fn adjust_for_scroll(&mut self, scroll_offset: i16, rows: u16) {
+        if scroll_offset == 0 {
+            return;
+        }
+        for mark in self.marks.values_mut() {
+            if let Bookmark::At(x, y) = *mark {
+                let new_y = y as i32 - scroll_offset as i32;
+                *mark = if (0..rows as i32).contains(&new_y) {
+                    Bookmark::At(x, new_y as u16)
+                } else {
+                    Bookmark::Stale
+                };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_and_get() {
+        let mut marks = Bookmarks::new();
+        assert_eq!(marks.get('a'), None);
+        marks.set('a', (3, 4));
+        marks.set('a', (5, 6));
+        assert_eq!(marks.get('a'), Some(Bookmark::At(5, 6)));
+        assert!(Bookmarks::is_valid_name('Z'));
+        assert!(!Bookmarks::is_valid_name('\x1b'));
+    }
+
+    #[test]
+    fn test_follow_scroll_until_stale() {
+        let mut marks = Bookmarks::new();
+        marks.set('s', (0, 3));
+        marks.set('h', (2, 0));
+
+        marks.adjust_for_scroll(2, 24);
+        assert_eq!(marks.get('s'), Some(Bookmark::At(0, 1)));
+        assert_eq!(marks.get('h'), Some(Bookmark::Stale));
+
+        // Stale bookmarks don't come back
+        marks.adjust_for_scroll(-5, 24);
+        assert_eq!(marks.get('s'), Some(Bookmark::At(0, 6)));
+        assert_eq!(marks.get('h'), Some(Bookmark::Stale));
+
+        marks.adjust_for_scroll(-18, 24);
+        assert_eq!(marks.get('s'), Some(Bookmark::Stale));
+    }
+}
@@ -0,0 +1,283 @@
+//! Highlight tracking for menu-driven programs
+//!
+//! Programs like mc, htop, dialog and fzf show the selected item only with
+//! reverse video or a background color, often leaving the terminal cursor
+//! where it was. Highlighted runs of text are found from cell attributes,
+//! and a run that wasn't there after the last output is taken as the
+//! selection moving.
+
+use crate::terminal::cell::Color;
+use crate::terminal::Screen;
+use std::collections::HashMap;
+
+/// Most highlights that may appear at once for one to be announced;
+/// more than this is a redraw rather than a selection moving
+const MAX_NEW: usize = 2;
+
+/// A run of highlighted text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Highlight {
+    /// Column of the first character of the text
+    pub x: u16,
+    pub y: u16,
+    pub text: String,
+
+    /// Whether the highlight spans the whole row
+    pub full_width: bool,
+
+    /// Whether this looks like a status bar rather than a selection: a
+    /// full-width bar on the first or last row, or one that changed
+    /// without moving off its row
+    pub status_bar: bool,
+}
+
+/// The background used by most of the screen
+///
+/// Cells drawn with any other background stand out from it.
+fn usual_background(screen: &Screen) -> Option<Color> {
+    let mut counts: HashMap<Option<Color>, usize> = HashMap::new();
+    for cell in screen.buffer.iter().flatten() {
+        if !cell.attrs.reverse {
+            *counts.entry(cell.attrs.background).or_default() += 1;
+        }
+    }
+    counts
+        .into_iter()
+        .max_by_key(|&(_, count)| count)
+        .and_then(|(background, _)| background)
+}
+
+/// Find runs of text shown in reverse video or an unusual background
+pub fn find_highlights(screen: &Screen) -> Vec<Highlight> {
+    let usual = usual_background(screen);
+    let mut highlights = Vec::new();
+
+    for (y, row) in screen.buffer.iter().enumerate() {
+        let mut x = 0;
+        while x < row.len() {
+            let attrs = row[x].attrs;
+            let end = row[x..]
+                .iter()
+                .position(|cell| cell.attrs != attrs)
+                .map_or(row.len(), |len| x + len);
+
+            if attrs.reverse || attrs.background != usual {
+                let run = &row[x..end];
+                let text: String = run
+                    .iter()
+                    .filter(|cell| !cell.is_wide_continuation)
+                    .map(|cell| cell.data)
+                    .collect();
+                let leading = run.iter().take_while(|cell| cell.data == ' ').count();
+                let text = text.trim();
+                if text.chars().count() > 1 {
+                    let full_width = x == 0 && end == row.len();
+                    let edge = y == 0 || y + 1 == screen.buffer.len();
+                    highlights.push(Highlight {
+                        x: (x + leading) as u16,
+                        y: y as u16,
+                        text: text.to_string(),
+                        full_width,
+                        status_bar: full_width && edge,
+                    });
+                }
+            }
+            x = end;
+        }
+    }
+    highlights
+}
+
+/// Remembers the highlights seen after the last output
+#[derive(Default)]
+pub struct HighlightTracker {
+    last: Vec<Highlight>,
+}
+
+impl HighlightTracker {
+    /// Create a tracker that has seen no highlights
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the current highlights
+    ///
+    /// Returns a highlight that has appeared or changed since the last
+    /// update, unless so many appeared that the screen was redrawn.
+    ///
+    /// A full-width bar that changed on the row it was already on is
+    /// marked as a status bar; a selection bar moves to another row.
+    pub fn update(&mut self, current: Vec<Highlight>) -> Option<Highlight> {
+        let new: Vec<&Highlight> = current
+            .iter()
+            .filter(|highlight| !self.last.contains(highlight))
+            .collect();
+        let moved = if new.len() <= MAX_NEW {
+            new.first().map(|&highlight| {
+                let mut highlight = highlight.clone();
+                let stayed = self
+                    .last
+                    .iter()
+                    .any(|last| last.full_width && last.y == highlight.y);
+                highlight.status_bar |= highlight.full_width && stayed;
+                highlight
+            })
+        } else {
+            None
+        };
+        self.last = current;
+        moved
+    }
+
+    /// Forget the highlights seen, such as when a program exits
+    pub fn reset(&mut self) {
+        self.last.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::terminal::cell::Attrs;
+    use crate::terminal::Emulator;
+
+    /// Draw text on a row with the given attributes
+    fn draw(screen: &mut Screen, x: usize, y: usize, text: &str, attrs: Attrs) {
+        for (i, ch) in text.chars().enumerate() {
+            let cell = &mut screen.buffer[y][x + i];
+            cell.data = ch;
+            cell.attrs = attrs;
+        }
+    }
+
+    const REVERSE: Attrs = Attrs {
+        reverse: true,
+        background: None,
+    };
+
+    #[test]
+    fn test_find_reverse_video() {
+        let mut screen = Screen::new(20, 4);
+        draw(&mut screen, 0, 0, "  Item one", Attrs::default());
+        draw(&mut screen, 0, 1, "  Item two    ", REVERSE);
+        assert_eq!(
+            find_highlights(&screen),
+            vec![Highlight {
+                x: 2,
+                y: 1,
+                text: "Item two".to_string(),
+                full_width: false,
+                status_bar: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_find_unusual_background() {
+        // Whole screen blue, as in dialog, with the selection in another color
+        let blue = Attrs {
+            reverse: false,
+            background: Some(Color::Indexed(4)),
+        };
+        let mut screen = Screen::new(20, 3);
+        for y in 0..3 {
+            draw(&mut screen, 0, y, &" ".repeat(20), blue);
+        }
+        draw(&mut screen, 2, 0, "Install", blue);
+        let selected = Attrs {
+            reverse: false,
+            background: Some(Color::Indexed(1)),
+        };
+        draw(&mut screen, 2, 1, "Remove", selected);
+
+        let found = find_highlights(&screen);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].text, "Remove");
+    }
+
+    #[test]
+    fn test_background_painted_by_erasing() {
+        // dialog sets a blue background and erases to paint the screen,
+        // then draws items on it with the selection in red
+        let mut emulator = Emulator::new(20, 4);
+        emulator
+            .process(
+                b"\x1b[44m\x1b[2J\x1b[1;3HInstall\x1b[2;3H\x1b[41mRemove\x1b[44m\x1b[3;3HUpgrade",
+            )
+            .unwrap();
+
+        let found = find_highlights(emulator.screen());
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].text, "Remove");
+
+        // Moving the selection announces the new item, not the old one
+        let mut tracker = HighlightTracker::new();
+        tracker.update(found);
+        emulator
+            .process(b"\x1b[2;3HRemove\x1b[3;3H\x1b[41mUpgrade\x1b[44m")
+            .unwrap();
+        let moved = tracker.update(find_highlights(emulator.screen())).unwrap();
+        assert_eq!(moved.text, "Upgrade");
+    }
+
+    #[test]
+    fn test_full_width_bar() {
+        let mut screen = Screen::new(20, 3);
+        draw(&mut screen, 0, 2, " NORMAL  main.rs [+]", REVERSE);
+        draw(&mut screen, 2, 0, "menu item", REVERSE);
+
+        let found = find_highlights(&screen);
+        assert_eq!(found.len(), 2);
+        assert!(!found[0].full_width);
+        assert!(found[1].full_width);
+        assert!(found[1].status_bar);
+        assert_eq!(found[1].text, "NORMAL  main.rs [+]");
+    }
+
+    #[test]
+    fn test_full_width_selection_bar_moves() {
+        // htop's selected process is a bar across the whole screen
+        let mut tracker = HighlightTracker::new();
+        let mut screen = Screen::new(20, 5);
+        draw(&mut screen, 0, 1, "  1 root  init      ", REVERSE);
+        tracker.update(find_highlights(&screen));
+
+        draw(&mut screen, 0, 1, "  1 root  init      ", Attrs::default());
+        draw(&mut screen, 0, 2, " 42 me    bash      ", REVERSE);
+        let moved = tracker.update(find_highlights(&screen)).unwrap();
+        assert_eq!((moved.y, moved.text.as_str()), (2, "42 me    bash"));
+        assert!(moved.full_width);
+        assert!(!moved.status_bar);
+
+        // The same bar changing in place is taken as a status bar
+        draw(&mut screen, 0, 2, " 42 me    vim       ", REVERSE);
+        let changed = tracker.update(find_highlights(&screen)).unwrap();
+        assert!(changed.status_bar);
+    }
+
+    #[test]
+    fn test_tracker_announces_moves() {
+        let mut tracker = HighlightTracker::new();
+        let at = |y: u16, text: &str| Highlight {
+            x: 0,
+            y,
+            text: text.to_string(),
+            full_width: false,
+            status_bar: false,
+        };
+
+        assert_eq!(
+            tracker.update(vec![at(0, "title"), at(3, "one")]),
+            Some(at(0, "title"))
+        );
+        assert_eq!(tracker.update(vec![at(0, "title"), at(3, "one")]), None);
+        assert_eq!(
+            tracker.update(vec![at(0, "title"), at(4, "two")]),
+            Some(at(4, "two"))
+        );
+
+        // A full redraw isn't announced
+        let redraw = (5..10).map(|y| at(y, "x")).collect();
+        assert_eq!(tracker.update(redraw), None);
+    }
+}
@@ -0,0 +1,167 @@
+//! Indentation announcement
+//!
+//! In Python, YAML and Makefiles the leading whitespace of a line carries
+//! meaning, but lines are spoken with it stripped. This tracks the
+//! indentation of reviewed lines so changes can be announced, by speech
+//! or by a tone whose pitch rises with the indentation.
+
+/// Columns between tab stops when counting tabs
+pub const TAB_WIDTH: usize = 8;
+
+/// Tone pitch for unindented lines, in Hz
+const TONE_BASE: f32 = 220.0;
+
+/// Widest indentation with its own pitch; deeper lines share it
+const TONE_MAX_WIDTH: usize = 48;
+
+/// How indentation is announced when reading lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndentationMode {
+    Off,
+    Speak,
+    Tone,
+}
+
+impl IndentationMode {
+    /// All modes in cycling order
+    pub const ALL: [IndentationMode; 3] = [
+        IndentationMode::Off,
+        IndentationMode::Speak,
+        IndentationMode::Tone,
+    ];
+
+    /// Parse a mode name as written in the config file
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|mode| mode.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Mode name as written in the config file and spoken to the user
+    pub fn name(self) -> &'static str {
+        match self {
+            IndentationMode::Off => "off",
+            IndentationMode::Speak => "speak",
+            IndentationMode::Tone => "tone",
+        }
+    }
+
+    /// The next mode, wrapping from `Tone` back to `Off`
+    pub fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|&m| m == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+}
+
+/// Width of a line's leading whitespace in columns
+///
+/// Tabs advance to the next tab stop. Returns None for blank lines,
+/// which have no meaningful indentation.
+pub fn indentation_width(line: &str) -> Option<usize> {
+    let mut width = 0;
+    for ch in line.chars() {
+        match ch {
+            ' ' => width += 1,
+            '\t' => width += TAB_WIDTH - width % TAB_WIDTH,
+            _ if ch.is_whitespace() => width += 1,
+            _ => return Some(width),
+        }
+    }
+    None
+}
+
+/// Spoken description of an indentation width
+pub fn describe(width: usize) -> String {
+    match width {
+        0 => "no indent".to_string(),
+        1 => "indent 1 space".to_string(),
+        n => format!("indent {} spaces", n),
+    }
+}
+
+/// Tone pitch for an indentation width, rising a semitone per column
+pub fn tone_frequency(width: usize) -> f32 {
+    TONE_BASE * 2f32.powf(width.min(TONE_MAX_WIDTH) as f32 / 12.0)
+}
+
+/// Remembers the indentation of the last reviewed line
+#[derive(Default)]
+pub struct IndentationTracker {
+    last: Option<usize>,
+}
+
+impl IndentationTracker {
+    /// Create a tracker with no previous line
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a reviewed line
+    ///
+    /// Returns its indentation width if it should be announced: when it
+    /// differs from the previous non-blank line, or every time if
+    /// `always` is set. Blank lines are never announced and don't reset
+    /// the comparison.
+    pub fn update(&mut self, line: &str, always: bool) -> Option<usize> {
+        let width = indentation_width(line)?;
+        let changed = self.last != Some(width);
+        self.last = Some(width);
+        if changed || always {
+            Some(width)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_indentation_width() {
+        assert_eq!(indentation_width("def f():"), Some(0));
+        assert_eq!(indentation_width("    return 1"), Some(4));
+        assert_eq!(indentation_width("\tall: build"), Some(8));
+        assert_eq!(indentation_width("  \tx"), Some(8));
+        assert_eq!(indentation_width("         "), None);
+        assert_eq!(indentation_width(""), None);
+    }
+
+    #[test]
+    fn test_tracker_announces_changes() {
+        let mut tracker = IndentationTracker::new();
+        assert_eq!(tracker.update("def f():", false), Some(0));
+        assert_eq!(tracker.update("    x = 1", false), Some(4));
+        assert_eq!(tracker.update("    y = 2", false), None);
+        assert_eq!(tracker.update("", false), None);
+        assert_eq!(tracker.update("    return x", false), None);
+        assert_eq!(tracker.update("print(f())", false), Some(0));
+    }
+
+    #[test]
+    fn test_tracker_always() {
+        let mut tracker = IndentationTracker::new();
+        assert_eq!(tracker.update("  a:", true), Some(2));
+        assert_eq!(tracker.update("  b:", true), Some(2));
+    }
+
+    #[test]
+    fn test_mode_names_and_description() {
+        assert_eq!(
+            IndentationMode::from_name("Tone"),
+            Some(IndentationMode::Tone)
+        );
+        assert_eq!(IndentationMode::Tone.next(), IndentationMode::Off);
+        assert_eq!(describe(0), "no indent");
+        assert_eq!(describe(1), "indent 1 space");
+        assert_eq!(describe(4), "indent 4 spaces");
+    }
+
+    #[test]
+    fn test_tone_frequency() {
+        assert_eq!(tone_frequency(0), 220.0);
+        assert!((tone_frequency(12) - 440.0).abs() < 0.01);
+        assert_eq!(tone_frequency(100), tone_frequency(TONE_MAX_WIDTH));
+    }
+}
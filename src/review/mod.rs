@@ -4,6 +4,18 @@
 //! It's independent of the terminal cursor and allows reading any part of the screen.
 //! Navigation and speech methods are implemented in `state/mod.rs`.
 
+pub mod blocks;
+pub mod bookmarks;
+pub mod highlight;
+pub mod indentation;
+pub mod output;
+pub mod regions;
+pub mod say_all;
+pub mod search;
+pub mod selection;
+pub mod table;
+pub mod words;
+
 /// Review cursor for navigating terminal content
 pub struct ReviewCursor {
     /// Current position (x, y)
@@ -31,6 +43,25 @@ impl ReviewCursor {
     }
 }
 
+/// Describe the review and terminal cursors for a "where am I" report
+///
+/// Rows and columns are spoken counting from 1. Returns the phrases to
+/// speak, in order.
+pub fn describe_position(review: (u16, u16), cursor: (u16, u16), size: (u16, u16)) -> Vec<String> {
+    let mut phrases = vec![format!("row {} column {}", review.1 + 1, review.0 + 1)];
+    if review == cursor {
+        phrases.push("at cursor".to_string());
+    } else {
+        phrases.push(format!(
+            "cursor row {} column {}",
+            cursor.1 + 1,
+            cursor.0 + 1
+        ));
+    }
+    phrases.push(format!("screen {} by {}", size.0, size.1));
+    phrases
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -63,4 +94,17 @@ mod tests {
         // Position should remain unchanged
         assert_eq!(cursor.pos, (10, 5));
     }
+
+    #[test]
+    fn test_describe_position() {
+        assert_eq!(
+            describe_position((4, 2), (0, 23), (80, 24)),
+            vec![
+                "row 3 column 5",
+                "cursor row 24 column 1",
+                "screen 80 by 24"
+            ]
+        );
+        assert_eq!(describe_position((7, 9), (7, 9), (132, 43))[1], "at cursor");
+    }
 }
@@ -0,0 +1,81 @@
+//! Finding the last command's output
+//!
+//! Shells with integration marks (OSC 133) say exactly where output
+//! starts and ends, which `Screen::last_output_range` reports. Without
+//! them, output is taken to be the lines between the previous prompt and
+//! the current one.
+
+use std::ops::Range;
+
+/// Find the output before the prompt at line `current`
+///
+/// Searches back for the previous line that `is_prompt` accepts. The
+/// output is the lines between the two prompts, without trailing blank
+/// lines. Returns None if there is no earlier prompt or no output.
+pub fn find_by_prompt(
+    lines: &[String],
+    current: usize,
+    is_prompt: impl Fn(&str) -> bool,
+) -> Option<Range<usize>> {
+    let current = current.min(lines.len());
+    let prompt = (0..current).rev().find(|&i| is_prompt(&lines[i]))?;
+    trim_blank_end(lines, prompt + 1..current)
+}
+
+/// The prompt on a command line: the text before input starts
+///
+/// Returns None if there is nothing before `input_column`.
+pub fn prompt_prefix(line: &str, input_column: usize) -> Option<String> {
+    let prefix: String = line.chars().take(input_column).collect();
+    let prefix = prefix.trim();
+    if prefix.is_empty() {
+        None
+    } else {
+        Some(prefix.to_string())
+    }
+}
+
+/// Drop blank lines from the end of a range, None if nothing is left
+pub fn trim_blank_end(lines: &[String], range: Range<usize>) -> Option<Range<usize>> {
+    let end = (range.start..range.end)
+        .rev()
+        .find(|&i| !lines[i].trim().is_empty())?
+        + 1;
+    Some(range.start..end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &[&str]) -> Vec<String> {
+        text.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_find_by_prompt() {
+        let text = lines(&["me@host:~$ ls", "a.txt", "b.txt", "", "me@host:~$"]);
+        let is_prompt = |line: &str| line.starts_with("me@host:~$");
+        assert_eq!(find_by_prompt(&text, 4, is_prompt), Some(1..3));
+    }
+
+    #[test]
+    fn test_no_output_or_prompt() {
+        let text = lines(&["me@host:~$ true", "", "me@host:~$"]);
+        let is_prompt = |line: &str| line.starts_with("me@host:~$");
+        assert_eq!(find_by_prompt(&text, 2, is_prompt), None);
+        assert_eq!(find_by_prompt(&text, 0, is_prompt), None);
+    }
+
+    #[test]
+    fn test_prompt_with_partly_typed_command() {
+        let text = lines(&["me@host:~$ ls", "a.txt", "me@host:~$ git sta"]);
+        let prefix = prompt_prefix(&text[2], 10).unwrap();
+        assert_eq!(prefix, "me@host:~$");
+        assert_eq!(
+            find_by_prompt(&text, 2, |line| line.starts_with(&prefix)),
+            Some(1..2)
+        );
+        assert_eq!(prompt_prefix("   $ ", 3), None);
+    }
+}
@@ -0,0 +1,243 @@
+//! User-defined screen regions
+//!
+//! A region is a named rectangle of the screen, such as a tmux status
+//! bar or the vim command line, defined in `[region.<name>]` config
+//! sections. Regions can be read with a hotkey, and watched regions are
+//! announced when their content changes.
+
+use std::ops::Range;
+use std::time::{Duration, Instant};
+
+/// How long a watched region must be unchanged before it is announced,
+/// so a redraw in progress isn't read half-finished
+pub const SETTLE: Duration = Duration::from_millis(200);
+
+/// A named screen region
+///
+/// Edges are 1-based and inclusive. Negative values count from the
+/// bottom or right, so -1 is the last row or column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub name: String,
+    pub top: i32,
+    pub bottom: i32,
+    pub left: i32,
+    pub right: i32,
+
+    /// Key that reads the region with alt
+    pub key: Option<char>,
+
+    /// Announce the region when its content changes
+    pub watch: bool,
+}
+
+/// Resolve a 1-based or negative edge to a 0-based index
+fn resolve(edge: i32, size: u16) -> Option<u16> {
+    let size = size as i32;
+    let index = match edge {
+        0 => return None,
+        e if e > 0 => e - 1,
+        e => size + e,
+    };
+    if (0..size).contains(&index) {
+        Some(index as u16)
+    } else {
+        None
+    }
+}
+
+impl Region {
+    /// Create a region covering the whole screen
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            top: 1,
+            bottom: -1,
+            left: 1,
+            right: -1,
+            key: None,
+            watch: false,
+        }
+    }
+
+    /// Rows and columns covered on a screen of the given size
+    ///
+    /// Returns None if the region is off screen or empty.
+    pub fn bounds(&self, size: (u16, u16)) -> Option<(Range<u16>, Range<u16>)> {
+        let (cols, rows) = size;
+        let top = resolve(self.top, rows)?;
+        let bottom = resolve(self.bottom, rows)?;
+        let left = resolve(self.left, cols)?;
+        let right = resolve(self.right, cols)?;
+        if top > bottom || left > right {
+            return None;
+        }
+        Some((top..bottom + 1, left..right + 1))
+    }
+
+    /// Non-blank lines of text in the region, trimmed
+    ///
+    /// `lines` are the screen rows.
+    pub fn content(&self, lines: &[String], size: (u16, u16)) -> Vec<String> {
+        let (rows, cols) = match self.bounds(size) {
+            Some(bounds) => bounds,
+            None => return Vec::new(),
+        };
+        rows.filter_map(|y| lines.get(y as usize))
+            .map(|line| {
+                line.chars()
+                    .skip(cols.start as usize)
+                    .take(cols.len())
+                    .collect::<String>()
+                    .trim()
+                    .to_string()
+            })
+            .filter(|text| !text.is_empty())
+            .collect()
+    }
+}
+
+/// Last seen content of a watched region
+#[derive(Default)]
+struct Watch {
+    content: Option<Vec<String>>,
+    changed_at: Option<Instant>,
+}
+
+/// The configured regions and the state of watched ones
+#[derive(Default)]
+pub struct Regions {
+    regions: Vec<Region>,
+    watches: Vec<Watch>,
+}
+
+impl Regions {
+    /// Track the given regions
+    pub fn new(regions: Vec<Region>) -> Self {
+        let watches = regions.iter().map(|_| Watch::default()).collect();
+        Self { regions, watches }
+    }
+
+    /// Find the region read by a key
+    pub fn for_key(&self, key: char) -> Option<&Region> {
+        self.regions.iter().find(|region| region.key == Some(key))
+    }
+
+    /// Check watched regions against the screen
+    ///
+    /// Returns the content of regions that changed and have since been
+    /// stable for `SETTLE`. The first look at a region only records it.
+    pub fn changed(
+        &mut self,
+        lines: &[String],
+        size: (u16, u16),
+        now: Instant,
+    ) -> Vec<Vec<String>> {
+        let mut announce = Vec::new();
+        for (region, watch) in self.regions.iter().zip(self.watches.iter_mut()) {
+            if !region.watch {
+                continue;
+            }
+            let content = region.content(lines, size);
+            match &watch.content {
+                None => watch.content = Some(content),
+                Some(last) if *last != content => {
+                    watch.content = Some(content);
+                    watch.changed_at = Some(now);
+                }
+                Some(last) => {
+                    if watch.changed_at.is_some_and(|at| now >= at + SETTLE) {
+                        watch.changed_at = None;
+                        announce.push(last.clone());
+                    }
+                }
+            }
+        }
+        announce
+    }
+
+    /// When the next changed region will have settled
+    pub fn deadline(&self) -> Option<Instant> {
+        self.watches
+            .iter()
+            .filter_map(|watch| watch.changed_at)
+            .map(|at| at + SETTLE)
+            .min()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn screen() -> Vec<String> {
+        vec![
+            "file.txt                 ".to_string(),
+            "hello world              ".to_string(),
+            "                         ".to_string(),
+            "[0] bash       12:00 Oct ".to_string(),
+        ]
+    }
+
+    const SIZE: (u16, u16) = (25, 4);
+
+    #[test]
+    fn test_bounds_from_edges() {
+        let mut region = Region::new("status");
+        region.top = -1;
+        assert_eq!(region.bounds(SIZE), Some((3..4, 0..25)));
+        region.left = -10;
+        region.right = -5;
+        assert_eq!(region.bounds(SIZE), Some((3..4, 15..21)));
+        region.top = 9;
+        assert_eq!(region.bounds(SIZE), None);
+    }
+
+    #[test]
+    fn test_content() {
+        let mut region = Region::new("clock");
+        region.top = -1;
+        region.left = 16;
+        region.right = 20;
+        assert_eq!(region.content(&screen(), SIZE), vec!["12:00"]);
+
+        let whole = Region::new("all");
+        assert_eq!(whole.content(&screen(), SIZE).len(), 3);
+    }
+
+    #[test]
+    fn test_watch_announces_settled_changes() {
+        let mut region = Region::new("top");
+        region.bottom = 1;
+        region.watch = true;
+        let mut regions = Regions::new(vec![region]);
+        let start = Instant::now();
+        let mut lines = screen();
+
+        // First look only records
+        assert!(regions.changed(&lines, SIZE, start).is_empty());
+        assert_eq!(regions.deadline(), None);
+
+        lines[0] = "other.txt".to_string();
+        assert!(regions.changed(&lines, SIZE, start).is_empty());
+        assert_eq!(regions.deadline(), Some(start + SETTLE));
+        assert!(regions.changed(&lines, SIZE, start + SETTLE / 2).is_empty());
+        assert_eq!(
+            regions.changed(&lines, SIZE, start + SETTLE),
+            vec![vec!["other.txt".to_string()]]
+        );
+        assert!(regions.changed(&lines, SIZE, start + SETTLE * 2).is_empty());
+    }
+
+    #[test]
+    fn test_for_key() {
+        let mut region = Region::new("status");
+        region.key = Some('s');
+        let regions = Regions::new(vec![region]);
+        assert_eq!(
+            regions.for_key('s').map(|r| r.name.as_str()),
+            Some("status")
+        );
+        assert!(regions.for_key('x').is_none());
+    }
+}
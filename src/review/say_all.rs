@@ -0,0 +1,128 @@
+//! Say-all: continuous reading
+//!
+//! Reads line after line, starting the next line once the synthesizer
+//! has finished the last one. Lines are indexes into `Screen::all_lines`,
+//! so reading can start in the scrollback and carry on down the screen.
+
+use std::time::{Duration, Instant};
+
+/// How often to check whether the current line has finished
+pub const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Grace period after starting a line before asking if speech is done,
+/// since backends may not report speaking straight away
+const START_GRACE: Duration = Duration::from_millis(150);
+
+/// Rough speaking time for text, for backends that can't report it
+///
+/// Assumes about 16 characters a second at the default rate of 50,
+/// scaling with the rate.
+pub fn estimate_duration(text: &str, rate: u8) -> Duration {
+    let chars_per_second = 8.0 + rate.min(100) as f32 * 0.16;
+    let chars = text.chars().count().max(1) as f32;
+    Duration::from_secs_f32(chars / chars_per_second) + Duration::from_millis(200)
+}
+
+/// State of a say-all in progress
+pub struct SayAll {
+    /// Next line to read
+    next: usize,
+
+    /// When the current line was started
+    started: Instant,
+
+    /// Estimated end of the current line, used when the backend
+    /// can't say whether it is still speaking
+    estimated_end: Instant,
+}
+
+impl SayAll {
+    /// Start reading at a line
+    pub fn new(from: usize) -> Self {
+        let now = Instant::now();
+        Self {
+            next: from,
+            started: now,
+            estimated_end: now,
+        }
+    }
+
+    /// Check if the current line is done and the next can start
+    ///
+    /// `speaking` is the backend's answer, or None if it can't tell.
+    pub fn ready(&self, speaking: Option<bool>, now: Instant) -> bool {
+        if now < self.started + START_GRACE && self.started != self.estimated_end {
+            return false;
+        }
+        match speaking {
+            Some(speaking) => !speaking,
+            None => now >= self.estimated_end,
+        }
+    }
+
+    /// Take the next non-blank line to read
+    ///
+    /// Returns None when there are no more lines.
+    pub fn next_line(&mut self, lines: &[String]) -> Option<usize> {
+        while self.next < lines.len() {
+            let line = self.next;
+            self.next += 1;
+            if !lines[line].trim().is_empty() {
+                return Some(line);
+            }
+        }
+        None
+    }
+
+    /// Note that a line has started speaking
+    pub fn started(&mut self, text: &str, rate: u8, now: Instant) {
+        self.started = now;
+        self.estimated_end = now + estimate_duration(text, rate);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_line_skips_blanks() {
+        let lines: Vec<String> = ["one", "", "   ", "two"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let mut say_all = SayAll::new(0);
+        assert_eq!(say_all.next_line(&lines), Some(0));
+        assert_eq!(say_all.next_line(&lines), Some(3));
+        assert_eq!(say_all.next_line(&lines), None);
+    }
+
+    #[test]
+    fn test_ready_waits_for_speech() {
+        let start = Instant::now();
+        let mut say_all = SayAll::new(0);
+        // Nothing spoken yet
+        assert!(say_all.ready(Some(false), start));
+
+        say_all.started("hello world", 50, start);
+        // Grace period before trusting the backend
+        assert!(!say_all.ready(Some(false), start + Duration::from_millis(10)));
+        assert!(!say_all.ready(Some(true), start + Duration::from_millis(500)));
+        assert!(say_all.ready(Some(false), start + Duration::from_millis(500)));
+    }
+
+    #[test]
+    fn test_ready_estimates_without_backend_support() {
+        let start = Instant::now();
+        let mut say_all = SayAll::new(0);
+        let text = "x".repeat(32);
+        say_all.started(&text, 50, start);
+        assert!(!say_all.ready(None, start + Duration::from_millis(1000)));
+        assert!(say_all.ready(None, start + Duration::from_millis(2300)));
+    }
+
+    #[test]
+    fn test_estimate_scales_with_rate() {
+        assert!(estimate_duration("some text", 100) < estimate_duration("some text", 0));
+    }
+}
@@ -0,0 +1,224 @@
+//! Text search over the screen and scrollback
+//!
+//! Lines are addressed by index into `Screen::all_lines`, so scrollback
+//! and screen are searched as one document. Columns are character
+//! positions, matching screen cells; the '\0' continuation cell after a
+//! wide character is skipped when matching so text spanning it is found.
+
+use crate::{Result, TdsrError};
+use regex::{Regex, RegexBuilder};
+
+/// Direction to search in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Forward,
+    Backward,
+}
+
+/// A match position: line index and starting column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Result of a search step
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Found {
+    pub at: Match,
+
+    /// True if the search passed the end (or start) and continued
+    /// from the other side
+    pub wrapped: bool,
+}
+
+/// A compiled search query
+pub struct Search {
+    /// Query as typed, for logging
+    pub query: String,
+
+    regex: Regex,
+}
+
+impl Search {
+    /// Compile a query, as plain text or as a regular expression
+    pub fn new(query: &str, is_regex: bool, ignore_case: bool) -> Result<Self> {
+        if query.is_empty() {
+            return Err(TdsrError::Config("Empty search".to_string()));
+        }
+        let source = if is_regex {
+            query.to_string()
+        } else {
+            regex::escape(query)
+        };
+        let regex = RegexBuilder::new(&source)
+            .case_insensitive(ignore_case)
+            .build()
+            .map_err(|e| TdsrError::Config(format!("Invalid search '{}': {}", query, e)))?;
+
+        Ok(Self {
+            query: query.to_string(),
+            regex,
+        })
+    }
+
+    /// Columns where the query matches in a line
+    fn columns(&self, line: &str) -> Vec<usize> {
+        // Match against the text without continuation cells, mapping
+        // byte offsets in it back to screen columns
+        let mut text = String::with_capacity(line.len());
+        let mut offsets = Vec::new();
+        for (column, ch) in line.chars().enumerate() {
+            if ch != '\0' {
+                offsets.push((text.len(), column));
+                text.push(ch);
+            }
+        }
+
+        self.regex
+            .find_iter(&text)
+            .filter(|m| !m.as_str().is_empty())
+            .filter_map(|m| {
+                offsets
+                    .binary_search_by_key(&m.start(), |&(offset, _)| offset)
+                    .ok()
+                    .map(|i| offsets[i].1)
+            })
+            .collect()
+    }
+
+    /// Find the next match after `from` (or before it, going backward)
+    ///
+    /// The match at `from` itself is skipped, so repeating a search moves
+    /// on. Wraps around the ends of `lines`.
+    pub fn find(&self, lines: &[String], from: Match, direction: Direction) -> Option<Found> {
+        let count = lines.len();
+        if count == 0 {
+            return None;
+        }
+        let start = from.line.min(count - 1);
+
+        // Visit every line once, then the start line again for matches
+        // on the other side of `from`
+        for step in 0..=count {
+            let (line, wrapped) = match direction {
+                Direction::Forward => {
+                    let line = start + step;
+                    (line % count, line >= count)
+                }
+                Direction::Backward => ((start + count * 2 - step) % count, step > start),
+            };
+            let columns = self.columns(&lines[line]);
+
+            let column = match (direction, step) {
+                (Direction::Forward, 0) => columns.into_iter().find(|&c| c > from.column),
+                (Direction::Backward, 0) => columns.into_iter().rev().find(|&c| c < from.column),
+                (Direction::Forward, _) => columns.into_iter().next(),
+                (Direction::Backward, _) => columns.into_iter().next_back(),
+            };
+            if let Some(column) = column {
+                return Some(Found {
+                    at: Match { line, column },
+                    wrapped,
+                });
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &[&str]) -> Vec<String> {
+        text.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn at(line: usize, column: usize) -> Match {
+        Match { line, column }
+    }
+
+    #[test]
+    fn test_forward_and_repeat() {
+        let text = lines(&["ok", "error: one", "fine", "ERROR: two error"]);
+        let search = Search::new("error", false, true).unwrap();
+
+        let found = search.find(&text, at(0, 0), Direction::Forward).unwrap();
+        assert_eq!(found.at, at(1, 0));
+        assert!(!found.wrapped);
+
+        let found = search.find(&text, found.at, Direction::Forward).unwrap();
+        assert_eq!(found.at, at(3, 0));
+        let found = search.find(&text, found.at, Direction::Forward).unwrap();
+        assert_eq!(found.at, at(3, 11));
+
+        // Past the end wraps to the top
+        let found = search.find(&text, found.at, Direction::Forward).unwrap();
+        assert_eq!(found.at, at(1, 0));
+        assert!(found.wrapped);
+    }
+
+    #[test]
+    fn test_backward() {
+        let text = lines(&["error a", "b", "error c error"]);
+        let search = Search::new("error", false, false).unwrap();
+
+        let found = search.find(&text, at(2, 8), Direction::Backward).unwrap();
+        assert_eq!(found.at, at(2, 0));
+        let found = search.find(&text, found.at, Direction::Backward).unwrap();
+        assert_eq!(found.at, at(0, 0));
+        let found = search.find(&text, found.at, Direction::Backward).unwrap();
+        assert_eq!(found.at, at(2, 8));
+        assert!(found.wrapped);
+    }
+
+    #[test]
+    fn test_case_sensitivity() {
+        let text = lines(&["Error", "error"]);
+        let search = Search::new("error", false, false).unwrap();
+        let found = search.find(&text, at(0, 0), Direction::Forward).unwrap();
+        assert_eq!(found.at, at(1, 0));
+    }
+
+    #[test]
+    fn test_regex_and_columns() {
+        let text = lines(&["café line 42", "no digits"]);
+        let search = Search::new(r"\d+", true, true).unwrap();
+        let found = search.find(&text, at(1, 0), Direction::Forward).unwrap();
+        // Column counts characters, not bytes
+        assert_eq!(found.at, at(0, 10));
+        assert!(found.wrapped);
+    }
+
+    #[test]
+    fn test_wide_characters() {
+        let text = lines(&["ok \u{65e5}\0\u{672c}\0\u{8a9e}\0 \u{1f600}\0!"]);
+        let search = Search::new("\u{65e5}\u{672c}\u{8a9e}", false, true).unwrap();
+        let found = search.find(&text, at(0, 0), Direction::Forward).unwrap();
+        assert_eq!(found.at, at(0, 3));
+
+        // Columns after wide characters count their continuation cells
+        let search = Search::new("\u{1f600}!", false, true).unwrap();
+        let found = search.find(&text, at(0, 0), Direction::Forward).unwrap();
+        assert_eq!(found.at, at(0, 10));
+    }
+
+    #[test]
+    fn test_only_match_is_found_again() {
+        let text = lines(&["a", "needle", "b"]);
+        let search = Search::new("needle", false, true).unwrap();
+        let found = search.find(&text, at(1, 0), Direction::Forward).unwrap();
+        assert_eq!(found.at, at(1, 0));
+        assert!(found.wrapped);
+    }
+
+    #[test]
+    fn test_not_found_and_errors() {
+        let text = lines(&["a", "b"]);
+        let search = Search::new("zzz", false, true).unwrap();
+        assert_eq!(search.find(&text, at(0, 0), Direction::Forward), None);
+        assert!(Search::new("(", true, true).is_err());
+        assert!(Search::new("", false, true).is_err());
+    }
+}
@@ -0,0 +1,202 @@
+//! Text selection for copying
+//!
+//! A selection runs from a start position to the review cursor. Linear
+//! selections read like a word processor: the rest of the first row, full
+//! rows in between and the start of the last row. Block selections take
+//! the same columns from every row, for copying a column out of a table
+//! or a box out of a full-screen program.
+
+use crate::terminal::Screen;
+
+/// A selection in progress, anchored where it was started
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Selection {
+    /// Position the selection was started at (x, y)
+    pub start: (u16, u16),
+
+    /// Whether this is a rectangular (block) selection
+    pub block: bool,
+}
+
+impl Selection {
+    /// Start a linear selection at a position
+    pub fn new(start: (u16, u16)) -> Self {
+        Self {
+            start,
+            block: false,
+        }
+    }
+
+    /// Text selected between the start and `end`, inclusive
+    pub fn text(&self, screen: &Screen, end: (u16, u16)) -> String {
+        if self.block {
+            block_text(screen, self.start, end)
+        } else {
+            linear_text(screen, self.start, end)
+        }
+    }
+
+    /// Describe the size of the selection, e.g. "3 rows by 12 columns"
+    pub fn extent(&self, end: (u16, u16)) -> String {
+        let rows = self.start.1.abs_diff(end.1) + 1;
+        let columns = self.start.0.abs_diff(end.0) + 1;
+        if self.block {
+            format!("{} by {}", count(rows, "row"), count(columns, "column"))
+        } else if rows == 1 {
+            count(columns, "character")
+        } else {
+            count(rows, "row")
+        }
+    }
+}
+
+fn count(n: u16, noun: &str) -> String {
+    if n == 1 {
+        format!("1 {}", noun)
+    } else {
+        format!("{} {}s", n, noun)
+    }
+}
+
+/// Copy text from a linear region of the screen
+///
+/// Multi-line selections include the end of the first line, full middle
+/// lines, and the beginning of the last line.
+pub fn linear_text(screen: &Screen, start: (u16, u16), end: (u16, u16)) -> String {
+    // Normalize so start is before end in reading order (row-major)
+    // Important: swap x and y together to maintain the linear selection
+    let (mut start, mut end) = (start, end);
+    if start.1 > end.1 || (start.1 == end.1 && start.0 > end.0) {
+        std::mem::swap(&mut start, &mut end);
+    }
+
+    let mut text = String::new();
+    let (cols, _) = screen.size;
+
+    for y in start.1..=end.1 {
+        // The first row starts at the selection start, others at column 0
+        let line_start = if y == start.1 { start.0 } else { 0 };
+
+        // The last row ends at the selection end, others at the last column
+        let line_end = if y == end.1 { end.0 } else { cols - 1 };
+
+        push_cells(&mut text, screen, y, line_start, line_end);
+
+        if y < end.1 {
+            text.push('\n');
+        }
+    }
+
+    text
+}
+
+/// Copy text from a rectangle of the screen
+///
+/// The corners may be given in any order. Trailing spaces are trimmed
+/// from each row so padding after a short cell isn't copied.
+pub fn block_text(screen: &Screen, start: (u16, u16), end: (u16, u16)) -> String {
+    let (left, right) = (start.0.min(end.0), start.0.max(end.0));
+    let (top, bottom) = (start.1.min(end.1), start.1.max(end.1));
+
+    let mut rows = Vec::new();
+    for y in top..=bottom {
+        let mut row = String::new();
+        push_cells(&mut row, screen, y, left, right);
+        rows.push(row.trim_end().to_string());
+    }
+
+    rows.join("\n")
+}
+
+/// Trim trailing spaces from each row and drop trailing blank rows
+///
+/// Used when copying to the edge of the screen, where a linear range picks
+/// up the padding of every row.
+pub fn trim_rows(text: &str) -> String {
+    let rows: Vec<&str> = text.lines().map(str::trim_end).collect();
+    let len = rows
+        .iter()
+        .rposition(|row| !row.is_empty())
+        .map_or(0, |i| i + 1);
+    rows[..len].join("\n")
+}
+
+fn push_cells(text: &mut String, screen: &Screen, y: u16, from: u16, to: u16) {
+    for x in from..=to {
+        if let Some(ch) = screen.get_char(x, y) {
+            // Skip wide character continuation cells
+            if ch != '\0' {
+                text.push(ch);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn screen_with(lines: &[&str]) -> Screen {
+        let mut screen = Screen::new(12, lines.len() as u16);
+        for (y, line) in lines.iter().enumerate() {
+            for (x, ch) in line.chars().enumerate() {
+                screen.buffer[y][x].data = ch;
+            }
+        }
+        screen
+    }
+
+    #[test]
+    fn test_block_text_takes_a_column() {
+        let screen = screen_with(&["NAME   SIZE", "a.txt  12", "b.rs   3400"]);
+        assert_eq!(block_text(&screen, (7, 0), (10, 2)), "SIZE\n12\n3400");
+    }
+
+    #[test]
+    fn test_block_text_any_corner_order() {
+        let screen = screen_with(&["NAME   SIZE", "a.txt  12", "b.rs   3400"]);
+        assert_eq!(block_text(&screen, (4, 2), (0, 1)), "a.txt\nb.rs");
+    }
+
+    #[test]
+    fn test_block_text_trims_each_row() {
+        let screen = screen_with(&["+--------+", "| hi     |", "| there  |"]);
+        assert_eq!(block_text(&screen, (2, 1), (7, 2)), "hi\nthere");
+    }
+
+    #[test]
+    fn test_linear_text_wraps_rows() {
+        let screen = screen_with(&["one two", "three four"]);
+        assert_eq!(linear_text(&screen, (4, 0), (4, 1)), "two     \nthree");
+    }
+
+    #[test]
+    fn test_selection_text_uses_mode() {
+        let screen = screen_with(&["ab cd", "ef gh"]);
+        let mut selection = Selection::new((3, 0));
+        assert_eq!(selection.text(&screen, (1, 1)), "cd       \nef");
+        selection.block = true;
+        assert_eq!(selection.text(&screen, (1, 1)), "b c\nf g");
+        assert_eq!(selection.text(&screen, (4, 1)), "cd\ngh");
+    }
+
+    #[test]
+    fn test_trim_rows() {
+        assert_eq!(trim_rows("two     \nthree  \n   \n  "), "two\nthree");
+        assert_eq!(trim_rows("a\n   \nb "), "a\n\nb");
+        assert_eq!(trim_rows("    "), "");
+    }
+
+    #[test]
+    fn test_extent() {
+        let mut selection = Selection::new((2, 5));
+        assert_eq!(selection.extent((2, 5)), "1 character");
+        assert_eq!(selection.extent((13, 5)), "12 characters");
+        assert_eq!(selection.extent((0, 7)), "3 rows");
+
+        selection.block = true;
+        assert_eq!(selection.extent((13, 7)), "3 rows by 12 columns");
+        assert_eq!(selection.extent((2, 5)), "1 row by 1 column");
+        assert_eq!(selection.extent((0, 3)), "3 rows by 3 columns");
+    }
+}
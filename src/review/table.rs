@@ -0,0 +1,216 @@
+//! Table detection for column-aware navigation
+//!
+//! Commands like `ls -l`, `ps`, `df` and `docker ps` print tables whose
+//! columns are aligned with spaces. A column boundary is a character
+//! position that is whitespace on every row of the table, so columns are
+//! inferred by growing a set of rows out from the review cursor line for
+//! as long as the rows line up with each other.
+
+use std::ops::Range;
+
+/// A table found on screen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Table {
+    /// Lines in the table; the first is taken as the header
+    pub rows: Range<usize>,
+
+    /// Starting character column of each table column
+    pub starts: Vec<usize>,
+}
+
+/// Mark positions that hold a non-space character in a line
+fn mark(occupied: &mut Vec<bool>, line: &str) {
+    for (i, ch) in line.chars().enumerate() {
+        if i >= occupied.len() {
+            occupied.resize(i + 1, false);
+        }
+        if !ch.is_whitespace() {
+            occupied[i] = true;
+        }
+    }
+}
+
+/// Runs of non-space characters in a line, as character ranges
+fn runs(line: &str) -> Vec<Range<usize>> {
+    let mut runs: Vec<Range<usize>> = Vec::new();
+    for (i, ch) in line.chars().enumerate() {
+        if ch.is_whitespace() {
+            continue;
+        }
+        match runs.last_mut() {
+            Some(run) if run.end == i => run.end = i + 1,
+            _ => runs.push(i..i + 1),
+        }
+    }
+    runs
+}
+
+/// Check if a line fits the columns marked so far
+///
+/// A line doesn't fit if it is blank, if it would leave fewer than two
+/// columns, or if it has text lying wholly in the space between columns,
+/// as a shell prompt above the table usually does. Text past the start of
+/// the last column is allowed, since last cells often contain spaces.
+fn fits(occupied: &[bool], line: &str) -> bool {
+    let last_start = match column_starts(occupied).last() {
+        Some(&start) => start,
+        None => return false,
+    };
+    let line_runs = runs(line);
+    if line_runs.is_empty() {
+        return false;
+    }
+    let in_gap = line_runs.iter().any(|run| {
+        run.start < last_start
+            && run
+                .clone()
+                .all(|i| !occupied.get(i).copied().unwrap_or(false))
+    });
+    if in_gap {
+        return false;
+    }
+
+    let mut candidate = occupied.to_vec();
+    mark(&mut candidate, line);
+    column_starts(&candidate).len() >= 2
+}
+
+/// Column starts: occupied positions that follow a free one
+fn column_starts(occupied: &[bool]) -> Vec<usize> {
+    (0..occupied.len())
+        .filter(|&i| occupied[i] && (i == 0 || !occupied[i - 1]))
+        .collect()
+}
+
+impl Table {
+    /// Find the table containing line `at`
+    ///
+    /// Rows are added above and below while they fit the columns found
+    /// so far, stopping at blank lines. Returns None if fewer than two
+    /// rows or two columns are found, or if no row is padded with spaces.
+    pub fn detect(lines: &[String], at: usize) -> Option<Self> {
+        let line = lines.get(at)?;
+        if line.trim().is_empty() {
+            return None;
+        }
+
+        let mut occupied = Vec::new();
+        mark(&mut occupied, line);
+        let mut rows = at..at + 1;
+
+        // Try a row, keeping it if it fits the columns found so far
+        let try_row = |occupied: &mut Vec<bool>, y: usize| -> bool {
+            if fits(occupied, &lines[y]) {
+                // Text past the start of the last column stays in that column
+                let last_start = column_starts(occupied).last().copied().unwrap_or(0);
+                mark(occupied, &lines[y]);
+                for cell in occupied.iter_mut().skip(last_start) {
+                    *cell = true;
+                }
+                true
+            } else {
+                false
+            }
+        };
+
+        while rows.start > 0 && try_row(&mut occupied, rows.start - 1) {
+            rows.start -= 1;
+        }
+        while rows.end < lines.len() && try_row(&mut occupied, rows.end) {
+            rows.end += 1;
+        }
+
+        let starts = column_starts(&occupied);
+        if starts.len() < 2 || rows.len() < 2 {
+            return None;
+        }
+
+        // Aligned output pads cells with runs of spaces somewhere, while
+        // prose with a chance alignment has only single spaces
+        let padded = lines[rows.clone()]
+            .iter()
+            .any(|line| line.trim().contains("  "));
+        if !padded {
+            return None;
+        }
+        Some(Self { rows, starts })
+    }
+
+    /// Number of columns
+    pub fn columns(&self) -> usize {
+        self.starts.len()
+    }
+
+    /// Column containing a character position
+    pub fn column_at(&self, x: usize) -> usize {
+        self.starts
+            .iter()
+            .rposition(|&start| start <= x)
+            .unwrap_or(0)
+    }
+
+    /// Text of a cell in a line, trimmed
+    pub fn cell(&self, line: &str, column: usize) -> String {
+        let start = self.starts[column];
+        let chars = line.chars().skip(start);
+        let text: String = match self.starts.get(column + 1) {
+            Some(&end) => chars.take(end - start).collect(),
+            None => chars.collect(),
+        };
+        text.trim().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &[&str]) -> Vec<String> {
+        text.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_detect_ps() {
+        let text = lines(&[
+            "$ ps",
+            "    PID TTY          TIME CMD",
+            "   4242 pts/0    00:00:00 bash",
+            "  12345 pts/0    00:00:00 ps",
+            "$ ",
+        ]);
+        let table = Table::detect(&text, 2).unwrap();
+        // The prompt lines don't line up and are left out
+        assert_eq!(table.rows, 1..4);
+        assert_eq!(table.columns(), 4);
+        assert_eq!(table.cell(&text[1], 0), "PID");
+        assert_eq!(table.cell(&text[3], 0), "12345");
+        assert_eq!(table.cell(&text[3], 2), "00:00:00");
+        assert_eq!(table.cell(&text[2], 3), "bash");
+    }
+
+    #[test]
+    fn test_detect_docker() {
+        let text = lines(&[
+            "CONTAINER ID   IMAGE     STATUS",
+            "1f2e3d4c5b6a   nginx     Up 2 hours",
+            "9a8b7c6d5e4f   redis     Exited (0) 3 days ago",
+            "",
+            "other text",
+        ]);
+        let table = Table::detect(&text, 0).unwrap();
+        assert_eq!(table.rows, 0..3);
+        assert_eq!(table.cell(&text[0], 0), "CONTAINER ID");
+        assert_eq!(table.cell(&text[1], 2), "Up 2 hours");
+        assert_eq!(table.cell(&text[2], 2), "Exited (0) 3 days ago");
+        assert_eq!(table.column_at(17), 1);
+        assert_eq!(table.column_at(100), 2);
+    }
+
+    #[test]
+    fn test_no_table() {
+        let text = lines(&["just some prose here", "and more of it", ""]);
+        assert_eq!(Table::detect(&text, 0), None);
+        assert_eq!(Table::detect(&text, 2), None);
+        assert_eq!(Table::detect(&lines(&["a  b"]), 0), None);
+    }
+}
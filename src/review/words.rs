@@ -0,0 +1,185 @@
+//! Word boundaries for review navigation
+//!
+//! Words are runs of characters between whitespace and the configured
+//! separator characters. In sub-word mode, words are also split at path
+//! separators, underscores, dots and hyphens, and where camelCase humps
+//! begin, so long paths and identifiers can be read in pieces.
+//!
+//! Lines are given as screen cells, with '\0' for wide character
+//! continuation cells, and positions are columns.
+
+use std::ops::Range;
+
+/// Characters that split sub-words
+pub const SUB_WORD_SEPARATORS: &[char] = &['/', '\\', '_', '.', '-'];
+
+/// How a line is split into words
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WordRules {
+    /// Characters that split words, in addition to whitespace
+    pub separators: Vec<char>,
+
+    /// Also stop at sub-word separators and camelCase humps
+    pub sub_words: bool,
+}
+
+impl WordRules {
+    /// Rules splitting on whitespace and the characters in `separators`
+    pub fn new(separators: &str, sub_words: bool) -> Self {
+        Self {
+            separators: separators.chars().filter(|c| !c.is_whitespace()).collect(),
+            sub_words,
+        }
+    }
+
+    /// Check if a character separates words
+    pub fn is_separator(&self, ch: char) -> bool {
+        ch.is_whitespace()
+            || self.separators.contains(&ch)
+            || (self.sub_words && SUB_WORD_SEPARATORS.contains(&ch))
+    }
+
+    /// Column ranges of the words in a line
+    pub fn spans(&self, line: &[char]) -> Vec<Range<usize>> {
+        let mut spans: Vec<Range<usize>> = Vec::new();
+        let mut in_word = false;
+        for (x, &ch) in line.iter().enumerate() {
+            if ch == '\0' {
+                // Continuation of a wide character stays with it
+                if in_word {
+                    if let Some(span) = spans.last_mut() {
+                        span.end = x + 1;
+                    }
+                }
+                continue;
+            }
+            if self.is_separator(ch) {
+                in_word = false;
+                continue;
+            }
+            let hump = in_word && self.sub_words && is_hump(line, x);
+            if in_word && !hump {
+                if let Some(span) = spans.last_mut() {
+                    span.end = x + 1;
+                }
+            } else {
+                spans.push(x..x + 1);
+            }
+            in_word = true;
+        }
+        spans
+    }
+
+    /// The word containing column `x`
+    pub fn word_at(&self, line: &[char], x: usize) -> Option<Range<usize>> {
+        self.spans(line).into_iter().find(|span| span.contains(&x))
+    }
+
+    /// Start of the word before the one at (or before) column `x`
+    pub fn prev_word_start(&self, line: &[char], x: usize) -> Option<usize> {
+        let spans = self.spans(line);
+        let current = spans
+            .iter()
+            .find(|span| span.contains(&x))
+            .map_or(x, |span| span.start);
+        spans
+            .iter()
+            .rev()
+            .find(|span| span.start < current)
+            .map(|span| span.start)
+    }
+
+    /// Start of the first word after column `x`
+    pub fn next_word_start(&self, line: &[char], x: usize) -> Option<usize> {
+        self.spans(line)
+            .into_iter()
+            .find(|span| span.start > x)
+            .map(|span| span.start)
+    }
+}
+
+/// Check if a camelCase hump starts at `x`
+///
+/// A hump starts at an uppercase letter after a lowercase letter or
+/// digit ("camelCase"), or at the last capital of an acronym that is
+/// followed by lowercase ("HTTPServer").
+fn is_hump(line: &[char], x: usize) -> bool {
+    let ch = line[x];
+    let prev = match x.checked_sub(1).map(|i| line[i]) {
+        Some(prev) => prev,
+        None => return false,
+    };
+    let next = line.get(x + 1).copied().unwrap_or(' ');
+    ch.is_uppercase()
+        && (prev.is_lowercase()
+            || prev.is_ascii_digit()
+            || (prev.is_uppercase() && next.is_lowercase()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(text: &str) -> Vec<char> {
+        text.chars().collect()
+    }
+
+    fn words(rules: &WordRules, text: &str) -> Vec<String> {
+        let line = chars(text);
+        rules
+            .spans(&line)
+            .into_iter()
+            .map(|span| line[span].iter().collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_default_splits_on_whitespace() {
+        let rules = WordRules::default();
+        assert_eq!(
+            words(&rules, "ls /usr/local/lib  foo_bar"),
+            vec!["ls", "/usr/local/lib", "foo_bar"]
+        );
+    }
+
+    #[test]
+    fn test_separators() {
+        let rules = WordRules::new(",=", false);
+        assert_eq!(words(&rules, "a=1,b=2"), vec!["a", "1", "b", "2"]);
+    }
+
+    #[test]
+    fn test_sub_words() {
+        let rules = WordRules::new("", true);
+        assert_eq!(
+            words(&rules, "/usr/local/lib/libfoo.so.1"),
+            vec!["usr", "local", "lib", "libfoo", "so", "1"]
+        );
+        assert_eq!(
+            words(&rules, "snake_case camelCaseWord HTTPServer utf8Decode"),
+            vec!["snake", "case", "camel", "Case", "Word", "HTTP", "Server", "utf8", "Decode"]
+        );
+    }
+
+    #[test]
+    fn test_wide_characters_stay_whole() {
+        let rules = WordRules::default();
+        let line = vec!['日', '\0', '本', '\0', ' ', 'x'];
+        assert_eq!(rules.spans(&line), vec![0..4, 5..6]);
+    }
+
+    #[test]
+    fn test_navigation() {
+        let rules = WordRules::new("", true);
+        let line = chars("cd some_dir");
+        assert_eq!(rules.word_at(&line, 5), Some(3..7));
+        assert_eq!(rules.word_at(&line, 2), None);
+        assert_eq!(rules.next_word_start(&line, 0), Some(3));
+        assert_eq!(rules.next_word_start(&line, 3), Some(8));
+        assert_eq!(rules.next_word_start(&line, 8), None);
+        assert_eq!(rules.prev_word_start(&line, 9), Some(3));
+        assert_eq!(rules.prev_word_start(&line, 7), Some(3));
+        assert_eq!(rules.prev_word_start(&line, 3), Some(0));
+        assert_eq!(rules.prev_word_start(&line, 1), None);
+    }
+}
@@ -182,6 +182,19 @@ impl Synth for NativeSynth {
 
         Ok(())
     }
+
+    fn is_speaking(&mut self) -> Option<bool> {
+        if !self.tts.supported_features().is_speaking {
+            return None;
+        }
+        match self.tts.is_speaking() {
+            Ok(speaking) => Some(speaking),
+            Err(e) => {
+                warn!("Failed to check speech state: {}", e);
+                None
+            }
+        }
+    }
 }
 
 #[cfg(test)]
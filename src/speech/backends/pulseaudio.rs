@@ -258,6 +258,14 @@ impl Synth for PulseAudioSynth {
         self.cancel_process();
         Ok(())
     }
+
+    fn is_speaking(&mut self) -> Option<bool> {
+        // Speech is over once the espeak-ng process has exited
+        match self.current_process.as_mut() {
+            Some(child) => Some(matches!(child.try_wait(), Ok(None))),
+            None => Some(false),
+        }
+    }
 }
 
 impl Drop for PulseAudioSynth {
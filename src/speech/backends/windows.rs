@@ -292,6 +292,11 @@ impl Synth for WindowsSynth {
         debug!("Canceling speech");
         self.send_command("x")
     }
+
+    fn is_speaking(&mut self) -> Option<bool> {
+        // The speech server doesn't report when an utterance finishes
+        None
+    }
 }
 
 impl Drop for WindowsSynth {
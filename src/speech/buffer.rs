@@ -1,18 +1,36 @@
 //! Speech buffer for accumulating text before speaking
 
 use log::debug;
+use std::time::{Duration, Instant};
+
+/// Longest a coalescing window may be stretched by continuous output,
+/// as a multiple of the window itself
+const MAX_HOLD_WINDOWS: u32 = 4;
 
 /// Buffer that accumulates text to be spoken
 ///
 /// Supports two modes:
 /// - Normal mode: accumulates all text until flushed
 /// - Line mode (line_pause): accumulates text and returns lines when completed
+///
+/// Text that isn't a completed line can be held for a short coalescing
+/// window, so output split across several PTY reads is spoken as one
+/// utterance instead of choppy fragments.
 pub struct SpeechBuffer {
     /// Current line being accumulated
     buffer: String,
 
     /// Lines ready to be spoken (when line_pause is enabled)
     pending_lines: Vec<String>,
+
+    /// How long to wait for more output before speaking held text
+    window: Duration,
+
+    /// When the currently held text first arrived
+    held_since: Option<Instant>,
+
+    /// When output was last added to the held text
+    last_output: Option<Instant>,
 }
 
 impl SpeechBuffer {
@@ -21,9 +39,46 @@ impl SpeechBuffer {
         Self {
             buffer: String::new(),
             pending_lines: Vec::new(),
+            window: Duration::ZERO,
+            held_since: None,
+            last_output: None,
         }
     }
 
+    /// Set the coalescing window (zero speaks after every read)
+    pub fn set_window(&mut self, window: Duration) {
+        self.window = window;
+    }
+
+    /// Note that a batch of output has been added to the buffer
+    ///
+    /// Each batch extends the coalescing window, up to a limit so that
+    /// continuous output can't hold speech back indefinitely.
+    pub fn mark_output(&mut self, now: Instant) {
+        if self.buffer.is_empty() {
+            return;
+        }
+        if self.held_since.is_none() {
+            self.held_since = Some(now);
+        }
+        self.last_output = Some(now);
+    }
+
+    /// When held text should be spoken, if any is held
+    pub fn deadline(&self) -> Option<Instant> {
+        if self.buffer.is_empty() {
+            return None;
+        }
+        let held_since = self.held_since?;
+        let last_output = self.last_output?;
+        Some((last_output + self.window).min(held_since + self.window * MAX_HOLD_WINDOWS))
+    }
+
+    /// Check if held text should be spoken now
+    pub fn is_due(&self, now: Instant) -> bool {
+        self.deadline().is_some_and(|deadline| now >= deadline)
+    }
+
     /// Write text to the buffer
     pub fn write(&mut self, text: &str) {
         self.buffer.push_str(text);
@@ -37,6 +92,9 @@ impl SpeechBuffer {
         if !self.buffer.is_empty() {
             let line = std::mem::take(&mut self.buffer);
             debug!("Line break: queuing {} chars for speech", line.len());
+            // A completed line isn't held, so a new window starts with the next one
+            self.held_since = None;
+            self.last_output = None;
             self.pending_lines.push(line);
         }
     }
@@ -59,6 +117,8 @@ impl SpeechBuffer {
     /// Clear the buffer and return its contents
     pub fn flush(&mut self) -> String {
         debug!("Flushing speech buffer: {} chars", self.buffer.len());
+        self.held_since = None;
+        self.last_output = None;
         std::mem::take(&mut self.buffer)
     }
 
@@ -145,4 +205,68 @@ mod tests {
         assert_eq!(buffer.pop(), None);
         assert!(buffer.is_empty());
     }
+
+    #[test]
+    fn test_zero_window_is_due_immediately() {
+        let mut buffer = SpeechBuffer::new();
+        let now = Instant::now();
+        assert_eq!(buffer.deadline(), None);
+
+        buffer.write("Hello");
+        buffer.mark_output(now);
+        assert!(buffer.is_due(now));
+    }
+
+    #[test]
+    fn test_window_coalesces_reads() {
+        let mut buffer = SpeechBuffer::new();
+        buffer.set_window(Duration::from_millis(50));
+        let start = Instant::now();
+
+        buffer.write("Compiling ");
+        buffer.mark_output(start);
+        assert!(!buffer.is_due(start + Duration::from_millis(30)));
+
+        // A second read before the window ends extends it
+        buffer.write("done");
+        buffer.mark_output(start + Duration::from_millis(30));
+        assert!(!buffer.is_due(start + Duration::from_millis(60)));
+        assert!(buffer.is_due(start + Duration::from_millis(80)));
+
+        assert_eq!(buffer.flush(), "Compiling done");
+        assert_eq!(buffer.deadline(), None);
+    }
+
+    #[test]
+    fn test_window_is_capped() {
+        let mut buffer = SpeechBuffer::new();
+        buffer.set_window(Duration::from_millis(50));
+        let start = Instant::now();
+
+        for i in 0..10 {
+            buffer.write("x");
+            buffer.mark_output(start + Duration::from_millis(i * 40));
+        }
+        assert_eq!(buffer.deadline(), Some(start + Duration::from_millis(200)));
+    }
+
+    #[test]
+    fn test_completed_lines_not_held() {
+        let mut buffer = SpeechBuffer::new();
+        buffer.set_window(Duration::from_millis(50));
+        let now = Instant::now();
+
+        buffer.write("li");
+        buffer.mark_output(now);
+        buffer.write("ne");
+        buffer.line_break();
+        assert_eq!(buffer.deadline(), None);
+        assert_eq!(buffer.drain_lines(), vec!["line".to_string()]);
+
+        // The next partial line gets a fresh window
+        let later = now + Duration::from_millis(500);
+        buffer.write("$ ");
+        buffer.mark_output(later);
+        assert_eq!(buffer.deadline(), Some(later + Duration::from_millis(50)));
+    }
 }
@@ -0,0 +1,372 @@
+//! Pronunciation dictionary
+//!
+//! Rewrites words and patterns before they are spoken, so that names like
+//! `kubectl` or long hex hashes come out as something listenable. Entries
+//! live in a plain text file (`~/.tdsr/dictionary`), one per line, with
+//! the fields separated by tabs (shown here as `<TAB>`):
+//!
+//! ```text
+//! # pattern<TAB>replacement<TAB>flags
+//! kubectl<TAB>cube control
+//! /dev/sda1<TAB>dev s d a 1
+//! [0-9a-f]{12,}<TAB>hash<TAB>r
+//! ```
+//!
+//! Lines that are just `#` or start with `# ` are comments, so patterns
+//! such as `#include` can still be entries.
+//!
+//! Flags are optional letters:
+//! - `r`: the pattern is a regular expression (`$1` etc. work in the replacement)
+//! - `c`: match case exactly (matching ignores case by default)
+//! - `p`: partial - a literal pattern also matches inside longer words
+//!
+//! A file is used rather than a config section because INI escaping
+//! would mangle the backslashes regular expressions need.
+
+use crate::{Result, TdsrError};
+use log::{debug, info};
+use regex::{NoExpand, Regex};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+
+/// A single pronunciation rule
+pub struct DictionaryEntry {
+    /// Text or regular expression to look for
+    pub pattern: String,
+
+    /// What to say instead
+    pub replacement: String,
+
+    /// Flag letters as written in the dictionary file
+    pub flags: String,
+
+    /// Compiled form of the pattern
+    regex: Regex,
+}
+
+impl DictionaryEntry {
+    /// Create an entry, compiling its pattern
+    pub fn new(pattern: &str, replacement: &str, flags: &str) -> Result<Self> {
+        if pattern.is_empty() {
+            return Err(TdsrError::Config("Empty dictionary pattern".to_string()));
+        }
+        if let Some(reason) = unwritable(pattern, replacement) {
+            return Err(TdsrError::Config(format!(
+                "Dictionary entry '{}' {}",
+                pattern, reason
+            )));
+        }
+
+        let is_regex = flags.contains('r');
+        let mut source = if is_regex {
+            pattern.to_string()
+        } else {
+            regex::escape(pattern)
+        };
+
+        // Literal entries match whole words unless marked partial. Only add
+        // a boundary next to word characters, so entries like "/dev/sda1"
+        // still match where they start with punctuation.
+        if !is_regex && !flags.contains('p') {
+            if pattern.starts_with(is_word_char) {
+                source = format!(r"\b{}", source);
+            }
+            if pattern.ends_with(is_word_char) {
+                source = format!(r"{}\b", source);
+            }
+        }
+
+        if !flags.contains('c') {
+            source = format!("(?i){}", source);
+        }
+
+        let regex = Regex::new(&source).map_err(|e| {
+            TdsrError::Config(format!("Invalid dictionary pattern '{}': {}", pattern, e))
+        })?;
+
+        Ok(Self {
+            pattern: pattern.to_string(),
+            replacement: replacement.to_string(),
+            flags: flags.to_string(),
+            regex,
+        })
+    }
+
+    /// Parse one line of the dictionary file
+    ///
+    /// Returns Ok(None) for blank lines and comments.
+    pub fn parse_line(line: &str) -> Result<Option<Self>> {
+        if line.trim().is_empty() || is_comment(line) {
+            return Ok(None);
+        }
+
+        let mut fields = line.split('\t');
+        let pattern = fields.next().unwrap_or_default();
+        let replacement = fields.next().ok_or_else(|| {
+            TdsrError::Config(format!("Dictionary line has no replacement: '{}'", line))
+        })?;
+        let flags = fields.next().unwrap_or_default().trim();
+
+        Self::new(pattern, replacement, flags).map(Some)
+    }
+
+    /// Format the entry as a dictionary file line
+    pub fn to_line(&self) -> String {
+        if self.flags.is_empty() {
+            format!("{}\t{}", self.pattern, self.replacement)
+        } else {
+            format!("{}\t{}\t{}", self.pattern, self.replacement, self.flags)
+        }
+    }
+
+    /// Apply this entry to text
+    fn apply(&self, text: &str) -> String {
+        if self.flags.contains('r') {
+            self.regex
+                .replace_all(text, self.replacement.as_str())
+                .into_owned()
+        } else {
+            self.regex
+                .replace_all(text, NoExpand(&self.replacement))
+                .into_owned()
+        }
+    }
+}
+
+/// Check if a dictionary file line is a comment
+fn is_comment(line: &str) -> bool {
+    line == "#" || line.starts_with("# ")
+}
+
+/// Why an entry can't be written as a dictionary file line, if it can't
+///
+/// Tabs and line breaks would split the fields, and a pattern that looks
+/// like a comment would be skipped when the file is read back.
+pub fn unwritable(pattern: &str, replacement: &str) -> Option<&'static str> {
+    let breaks_line = |ch| matches!(ch, '\t' | '\n' | '\r');
+    if pattern.contains(breaks_line) || replacement.contains(breaks_line) {
+        Some("contains a tab or line break")
+    } else if pattern.starts_with("# ") {
+        Some("would read as a comment")
+    } else {
+        None
+    }
+}
+
+/// Check for characters that regex `\b` treats as part of a word
+fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
+/// Ordered list of pronunciation rules
+///
+/// Entries are applied in file order, each to the output of the last.
+#[derive(Default)]
+pub struct Dictionary {
+    entries: Vec<DictionaryEntry>,
+}
+
+impl Dictionary {
+    /// Create an empty dictionary
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load the dictionary file
+    ///
+    /// A missing file gives an empty dictionary. Bad lines are logged
+    /// and skipped so one typo doesn't disable every entry.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            debug!("No dictionary file at {:?}", path);
+            return Ok(Self::new());
+        }
+
+        let text = std::fs::read_to_string(path)?;
+        let dictionary = Self::parse(&text);
+        info!(
+            "Loaded {} dictionary entries from {:?}",
+            dictionary.len(),
+            path
+        );
+        Ok(dictionary)
+    }
+
+    /// Parse dictionary file contents
+    pub fn parse(text: &str) -> Self {
+        let mut dictionary = Self::new();
+        for (number, line) in text.lines().enumerate() {
+            match DictionaryEntry::parse_line(line) {
+                Ok(Some(entry)) => dictionary.entries.push(entry),
+                Ok(None) => {}
+                Err(e) => info!("Skipping dictionary line {}: {}", number + 1, e),
+            }
+        }
+        dictionary
+    }
+
+    /// Add an entry and append it to the dictionary file
+    ///
+    /// Appending leaves the user's existing lines and comments untouched.
+    pub fn add(&mut self, entry: DictionaryEntry, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        // Start on a fresh line if the file doesn't end with a newline
+        let needs_newline = std::fs::read(path)
+            .map(|data| data.last().is_some_and(|&b| b != b'\n'))
+            .unwrap_or(false);
+
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        if needs_newline {
+            writeln!(file)?;
+        }
+        writeln!(file, "{}", entry.to_line())?;
+
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    /// Rewrite text using every entry in order
+    pub fn apply(&self, text: &str) -> String {
+        self.entries
+            .iter()
+            .fold(text.to_string(), |text, entry| entry.apply(&text))
+    }
+
+    /// Number of entries
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if there are no entries
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_literal_whole_word_ignores_case() {
+        let dict = Dictionary::parse("kubectl\tcube control\nnginx\tengine x\n");
+        assert_eq!(dict.len(), 2);
+        assert_eq!(
+            dict.apply("Kubectl get pods via nginx"),
+            "cube control get pods via engine x"
+        );
+        // Not inside longer words
+        assert_eq!(dict.apply("kubectlx"), "kubectlx");
+    }
+
+    #[test]
+    fn test_literal_starting_with_punctuation() {
+        let dict = Dictionary::parse("/dev/sda1\tdev s d a 1\n");
+        assert_eq!(dict.apply("mount /dev/sda1 /mnt"), "mount dev s d a 1 /mnt");
+        assert_eq!(dict.apply("/dev/sda12"), "/dev/sda12");
+    }
+
+    #[test]
+    fn test_case_sensitive_and_partial() {
+        let dict = Dictionary::parse("NaN\tnot a number\tc\nfoo\tbar\tp\n");
+        assert_eq!(dict.apply("NaN nan"), "not a number nan");
+        assert_eq!(dict.apply("food"), "bard");
+    }
+
+    #[test]
+    fn test_regex_with_captures() {
+        let dict = Dictionary::parse(
+            "([0-9a-f]{8})-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}\tuuid $1\tr\n\
+             [0-9a-f]{12,}\thash\tr\n",
+        );
+        assert_eq!(
+            dict.apply("commit 3f2a9c1be07d4e5f8a9b0c1d2e3f4a5b6c7d8e9f"),
+            "commit hash"
+        );
+        assert_eq!(
+            dict.apply("id 123e4567-e89b-12d3-a456-426614174000"),
+            "id uuid 123e4567"
+        );
+    }
+
+    #[test]
+    fn test_literal_replacement_not_expanded() {
+        let dict = Dictionary::parse("price\t$1 each\n");
+        assert_eq!(dict.apply("price"), "$1 each");
+    }
+
+    #[test]
+    fn test_skips_comments_and_bad_lines() {
+        let dict = Dictionary::parse("# comment\n\nno replacement\n(unclosed\tx\tr\nok\tfine\n");
+        assert_eq!(dict.len(), 1);
+        assert_eq!(dict.apply("ok"), "fine");
+    }
+
+    #[test]
+    fn test_entry_round_trip() {
+        for (pattern, replacement, flags) in [
+            ("#include", "include", ""),
+            ("#!/bin/sh", "shebang shell", "p"),
+            ("#", "hash", "c"),
+            ("#[0-9]+", "number", "r"),
+        ] {
+            let entry = DictionaryEntry::new(pattern, replacement, flags).unwrap();
+            let line = entry.to_line();
+            let parsed = DictionaryEntry::parse_line(&line).unwrap().unwrap();
+            assert_eq!(parsed.pattern, pattern);
+            assert_eq!(parsed.replacement, replacement);
+            assert_eq!(parsed.flags, flags);
+        }
+
+        let dict = Dictionary::parse(
+            "# comment
+#
+#include	include
+",
+        );
+        assert_eq!(dict.apply("#include <stdio.h>"), "include <stdio.h>");
+    }
+
+    #[test]
+    fn test_unwritable_entries_refused() {
+        assert!(DictionaryEntry::new("a\tb", "x", "").is_err());
+        assert!(DictionaryEntry::new("ab", "x\ny", "").is_err());
+        assert!(DictionaryEntry::new("# x", "y", "").is_err());
+        assert_eq!(unwritable("#define", "define"), None);
+        assert_eq!(
+            unwritable("a\tb", "x"),
+            Some("contains a tab or line break")
+        );
+    }
+
+    #[test]
+    fn test_add_appends_to_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("tdsr").join("dictionary");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "# my words\nnginx\tengine x").unwrap();
+
+        let mut dict = Dictionary::load(&path).unwrap();
+        dict.add(
+            DictionaryEntry::new("kubectl", "cube control", "").unwrap(),
+            &path,
+        )
+        .unwrap();
+        assert_eq!(dict.apply("kubectl"), "cube control");
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(text, "# my words\nnginx\tengine x\nkubectl\tcube control\n");
+        assert_eq!(Dictionary::load(&path).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_missing_file_is_empty() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let dict = Dictionary::load(&dir.path().join("missing")).unwrap();
+        assert!(dict.is_empty());
+    }
+}
@@ -0,0 +1,175 @@
+//! Flood control for large output bursts
+//!
+//! Speaking every line of `cat bigfile` or a noisy build leaves speech
+//! minutes behind the screen. Flood control watches how much output is
+//! being spoken per second. Past a limit it stops speaking individual
+//! lines and only counts them; once output has been quiet for a while
+//! it reports how many lines were skipped so a summary can be spoken.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Length of the window output rates are measured over
+const RATE_WINDOW: Duration = Duration::from_secs(1);
+
+/// Rate-based speech throttle
+pub struct FloodControl {
+    /// Lines per second that start a flood (0 = no line limit)
+    max_lines: usize,
+
+    /// Bytes per second that start a flood (0 = no byte limit)
+    max_bytes: usize,
+
+    /// Quiet time after which a flood is over
+    settle: Duration,
+
+    /// Recent output (time, lines, bytes) within the rate window
+    recent: VecDeque<(Instant, usize, usize)>,
+
+    /// Lines skipped in the current flood (None = not flooding)
+    skipped: Option<usize>,
+
+    /// When output was last seen during a flood
+    last_output: Instant,
+}
+
+impl FloodControl {
+    /// Create a throttle with the given per-second limits
+    pub fn new(max_lines: usize, max_bytes: usize, settle: Duration) -> Self {
+        Self {
+            max_lines,
+            max_bytes,
+            settle,
+            recent: VecDeque::new(),
+            skipped: None,
+            last_output: Instant::now(),
+        }
+    }
+
+    /// Record output about to be spoken
+    ///
+    /// Returns true if it should be spoken, false if it is part of a
+    /// flood and should be skipped.
+    pub fn observe(&mut self, text: &str, now: Instant) -> bool {
+        let lines = text.lines().count().max(1);
+        let bytes = text.len();
+
+        self.recent.push_back((now, lines, bytes));
+        while self
+            .recent
+            .front()
+            .is_some_and(|(when, _, _)| now.duration_since(*when) > RATE_WINDOW)
+        {
+            self.recent.pop_front();
+        }
+
+        if let Some(skipped) = self.skipped.as_mut() {
+            *skipped += lines;
+            self.last_output = now;
+            return false;
+        }
+
+        let total_lines: usize = self.recent.iter().map(|(_, l, _)| l).sum();
+        let total_bytes: usize = self.recent.iter().map(|(_, _, b)| b).sum();
+        let over_lines = self.max_lines > 0 && total_lines > self.max_lines;
+        let over_bytes = self.max_bytes > 0 && total_bytes > self.max_bytes;
+
+        if over_lines || over_bytes {
+            self.skipped = Some(lines);
+            self.last_output = now;
+            return false;
+        }
+        true
+    }
+
+    /// Check whether a flood has just settled
+    ///
+    /// Returns the number of skipped lines once output has been quiet
+    /// for the settle time, ending the flood.
+    pub fn check_settled(&mut self, now: Instant) -> Option<usize> {
+        if self.skipped.is_some() && now.duration_since(self.last_output) >= self.settle {
+            self.recent.clear();
+            return self.skipped.take();
+        }
+        None
+    }
+
+    /// When the current flood will settle if no more output arrives
+    pub fn settle_deadline(&self) -> Option<Instant> {
+        self.skipped.map(|_| self.last_output + self.settle)
+    }
+
+    /// Check if output is currently being skipped
+    pub fn is_flooding(&self) -> bool {
+        self.skipped.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(n: usize) -> String {
+        vec!["output line"; n].join("\n")
+    }
+
+    #[test]
+    fn test_normal_output_is_spoken() {
+        let mut flood = FloodControl::new(10, 0, Duration::from_millis(500));
+        let start = Instant::now();
+        for i in 0..5 {
+            assert!(flood.observe("hello", start + Duration::from_millis(i * 100)));
+        }
+        assert!(!flood.is_flooding());
+    }
+
+    #[test]
+    fn test_line_limit_starts_flood_and_counts() {
+        let mut flood = FloodControl::new(10, 0, Duration::from_millis(500));
+        let start = Instant::now();
+        assert!(flood.observe(&lines(8), start));
+        assert!(!flood.observe(&lines(5), start + Duration::from_millis(10)));
+        assert!(!flood.observe(&lines(400), start + Duration::from_millis(20)));
+        assert!(flood.is_flooding());
+
+        // Not settled while output keeps arriving
+        assert_eq!(
+            flood.check_settled(start + Duration::from_millis(100)),
+            None
+        );
+        assert_eq!(
+            flood.settle_deadline(),
+            Some(start + Duration::from_millis(520))
+        );
+
+        assert_eq!(
+            flood.check_settled(start + Duration::from_millis(520)),
+            Some(405)
+        );
+        assert!(!flood.is_flooding());
+        assert!(flood.observe("prompt $", start + Duration::from_millis(600)));
+    }
+
+    #[test]
+    fn test_byte_limit() {
+        let mut flood = FloodControl::new(0, 100, Duration::from_millis(500));
+        let start = Instant::now();
+        assert!(flood.observe(&"x".repeat(60), start));
+        assert!(!flood.observe(&"x".repeat(60), start));
+    }
+
+    #[test]
+    fn test_rate_window_slides() {
+        let mut flood = FloodControl::new(10, 0, Duration::from_millis(500));
+        let start = Instant::now();
+        assert!(flood.observe(&lines(8), start));
+        // The earlier burst has left the one second window
+        assert!(flood.observe(&lines(8), start + Duration::from_millis(1500)));
+    }
+
+    #[test]
+    fn test_zero_limits_disable() {
+        let mut flood = FloodControl::new(0, 0, Duration::from_millis(500));
+        assert!(flood.observe(&lines(10_000), Instant::now()));
+    }
+}
@@ -0,0 +1,193 @@
+//! Speech history for reviewing recent utterances
+//!
+//! Output often arrives in bursts faster than the user can follow.
+//! Every utterance that reaches `State::speak` is recorded here so the
+//! user can step back through what was said, repeat it, or copy it.
+
+use std::collections::VecDeque;
+
+/// Default number of utterances kept in history
+pub const DEFAULT_HISTORY_SIZE: usize = 100;
+
+/// Bounded history of spoken utterances with a review position
+///
+/// The review position starts past the newest entry. Stepping backward
+/// moves toward older utterances; recording a new utterance resets the
+/// position so the next step backward starts from the newest entry again.
+pub struct SpeechHistory {
+    /// Recorded utterances, oldest first
+    entries: VecDeque<String>,
+
+    /// Maximum number of utterances to keep
+    capacity: usize,
+
+    /// Index of the entry being reviewed (None = not reviewing)
+    position: Option<usize>,
+}
+
+impl SpeechHistory {
+    /// Create an empty history holding at most `capacity` utterances
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity.min(DEFAULT_HISTORY_SIZE)),
+            capacity,
+            position: None,
+        }
+    }
+
+    /// Record an utterance
+    ///
+    /// Blank text and immediate repeats of the newest entry are ignored.
+    /// The oldest entry is dropped once the history is full.
+    pub fn push(&mut self, text: &str) {
+        self.position = None;
+
+        if self.capacity == 0 || text.trim().is_empty() {
+            return;
+        }
+        if self.entries.back().is_some_and(|last| last == text) {
+            return;
+        }
+
+        if self.entries.len() >= self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(text.to_string());
+    }
+
+    /// Step to the previous (older) utterance
+    ///
+    /// Returns None when already at the oldest entry or the history is empty.
+    pub fn step_back(&mut self) -> Option<&str> {
+        let idx = match self.position {
+            None if !self.entries.is_empty() => self.entries.len() - 1,
+            Some(idx) if idx > 0 => idx - 1,
+            _ => return None,
+        };
+        self.position = Some(idx);
+        self.entries.get(idx).map(String::as_str)
+    }
+
+    /// Step to the next (newer) utterance
+    ///
+    /// Returns None when already at the newest entry or not reviewing.
+    pub fn step_forward(&mut self) -> Option<&str> {
+        match self.position {
+            Some(idx) if idx + 1 < self.entries.len() => {
+                self.position = Some(idx + 1);
+                self.entries.get(idx + 1).map(String::as_str)
+            }
+            _ => None,
+        }
+    }
+
+    /// The utterance being reviewed, or the newest one if not reviewing
+    pub fn current(&self) -> Option<&str> {
+        match self.position {
+            Some(idx) => self.entries.get(idx).map(String::as_str),
+            None => self.last(),
+        }
+    }
+
+    /// The most recently recorded utterance
+    pub fn last(&self) -> Option<&str> {
+        self.entries.back().map(String::as_str)
+    }
+
+    /// Number of recorded utterances
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if no utterances have been recorded
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl Default for SpeechHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_SIZE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_and_last() {
+        let mut history = SpeechHistory::new(10);
+        assert!(history.is_empty());
+        assert_eq!(history.last(), None);
+
+        history.push("one");
+        history.push("two");
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.last(), Some("two"));
+    }
+
+    #[test]
+    fn test_ignores_blank_and_repeats() {
+        let mut history = SpeechHistory::new(10);
+        history.push("");
+        history.push("   ");
+        history.push("hello");
+        history.push("hello");
+        assert_eq!(history.len(), 1);
+    }
+
+    #[test]
+    fn test_bounded() {
+        let mut history = SpeechHistory::new(3);
+        for text in ["a", "b", "c", "d"] {
+            history.push(text);
+        }
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.step_back(), Some("d"));
+        assert_eq!(history.step_back(), Some("c"));
+        assert_eq!(history.step_back(), Some("b"));
+        // "a" was dropped
+        assert_eq!(history.step_back(), None);
+        assert_eq!(history.current(), Some("b"));
+    }
+
+    #[test]
+    fn test_step_backward_and_forward() {
+        let mut history = SpeechHistory::new(10);
+        history.push("one");
+        history.push("two");
+        history.push("three");
+
+        // Not reviewing yet - next has nowhere to go
+        assert_eq!(history.step_forward(), None);
+
+        assert_eq!(history.step_back(), Some("three"));
+        assert_eq!(history.step_back(), Some("two"));
+        assert_eq!(history.current(), Some("two"));
+        assert_eq!(history.step_forward(), Some("three"));
+        assert_eq!(history.step_forward(), None);
+        assert_eq!(history.current(), Some("three"));
+    }
+
+    #[test]
+    fn test_push_resets_position() {
+        let mut history = SpeechHistory::new(10);
+        history.push("one");
+        history.push("two");
+        assert_eq!(history.step_back(), Some("two"));
+        assert_eq!(history.step_back(), Some("one"));
+
+        history.push("three");
+        assert_eq!(history.current(), Some("three"));
+        assert_eq!(history.step_back(), Some("three"));
+    }
+
+    #[test]
+    fn test_zero_capacity() {
+        let mut history = SpeechHistory::new(0);
+        history.push("anything");
+        assert!(history.is_empty());
+        assert_eq!(history.step_back(), None);
+    }
+}
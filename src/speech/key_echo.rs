@@ -0,0 +1,448 @@
+//! Key echo
+//!
+//! Decides what to say as the user types. Characters are only spoken once
+//! the terminal echoes them back, so a password prompt that doesn't echo
+//! stays silent. Depending on the mode, the word just finished is spoken
+//! when a space or punctuation is typed. Control keys are named as they
+//! are pressed, and pasted text is summarized rather than spelled out.
+
+/// Bracketed paste start and end markers
+const PASTE_START: &[u8] = b"\x1b[200~";
+const PASTE_END: &[u8] = b"\x1b[201~";
+
+/// What is spoken as the user types
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyEchoMode {
+    None,
+    Characters,
+    Words,
+    Both,
+}
+
+impl KeyEchoMode {
+    /// All modes in cycling order
+    pub const ALL: [KeyEchoMode; 4] = [
+        KeyEchoMode::None,
+        KeyEchoMode::Characters,
+        KeyEchoMode::Words,
+        KeyEchoMode::Both,
+    ];
+
+    /// Parse a mode name as written in the config file
+    ///
+    /// The old boolean values are accepted: true means characters.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "true" => Some(KeyEchoMode::Characters),
+            "false" => Some(KeyEchoMode::None),
+            name => Self::ALL.into_iter().find(|mode| mode.name() == name),
+        }
+    }
+
+    /// Mode name as written in the config file and spoken to the user
+    pub fn name(self) -> &'static str {
+        match self {
+            KeyEchoMode::None => "none",
+            KeyEchoMode::Characters => "characters",
+            KeyEchoMode::Words => "words",
+            KeyEchoMode::Both => "both",
+        }
+    }
+
+    /// The next mode, wrapping from `Both` back to `None`
+    pub fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|&m| m == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    /// Are typed characters spoken?
+    pub fn characters(self) -> bool {
+        matches!(self, KeyEchoMode::Characters | KeyEchoMode::Both)
+    }
+
+    /// Are completed words spoken?
+    pub fn words(self) -> bool {
+        matches!(self, KeyEchoMode::Words | KeyEchoMode::Both)
+    }
+}
+
+/// Feedback for keys typed while input is hidden (e.g. passwords)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasswordEcho {
+    /// Say "star" for each key
+    Star,
+    /// Ring the terminal bell for each key
+    Bell,
+    /// Say nothing
+    None,
+}
+
+impl PasswordEcho {
+    /// Parse a feedback name as written in the config file
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "star" => Some(PasswordEcho::Star),
+            "bell" => Some(PasswordEcho::Bell),
+            "none" => Some(PasswordEcho::None),
+            _ => None,
+        }
+    }
+}
+
+/// Speech for a character the terminal echoed back
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Echo {
+    /// The character itself, to be spoken as a letter
+    pub character: Option<char>,
+
+    /// The word the character completed
+    pub word: Option<String>,
+}
+
+/// Tracks typed keys and their echoes
+pub struct KeyEcho {
+    mode: KeyEchoMode,
+
+    /// Character typed but not yet echoed
+    pending: Option<char>,
+
+    /// Word being typed
+    word: String,
+
+    /// Pasted text (without whitespace) still expected to be echoed
+    paste_echo: String,
+
+    /// Whether the terminal is hiding typed input
+    input_hidden: bool,
+}
+
+impl KeyEcho {
+    /// Create a tracker for the given mode
+    pub fn new(mode: KeyEchoMode) -> Self {
+        Self {
+            mode,
+            pending: None,
+            word: String::new(),
+            paste_echo: String::new(),
+            input_hidden: false,
+        }
+    }
+
+    /// Current mode
+    pub fn mode(&self) -> KeyEchoMode {
+        self.mode
+    }
+
+    /// Change mode
+    pub fn set_mode(&mut self, mode: KeyEchoMode) {
+        self.mode = mode;
+        self.word.clear();
+    }
+
+    /// Note whether the terminal is hiding typed input
+    ///
+    /// Returns true when input has just become hidden, so the change
+    /// can be announced once. Typed characters are never spoken while
+    /// input is hidden, even if something echoes them.
+    pub fn set_input_hidden(&mut self, hidden: bool) -> bool {
+        let started = hidden && !self.input_hidden;
+        self.input_hidden = hidden;
+        if hidden {
+            self.word.clear();
+        }
+        started
+    }
+
+    /// Record input being sent to the shell
+    ///
+    /// Returns text to speak straight away: the name of a control key
+    /// or a summary of pasted text.
+    pub fn key_typed(&mut self, input: &[u8]) -> Option<String> {
+        self.pending = None;
+        self.paste_echo.clear();
+
+        if let Some(text) = bracketed_paste(input) {
+            self.word.clear();
+            return self.pasted(&text);
+        }
+
+        if input.first() == Some(&0x1b) {
+            // Escape sequences (arrows, function keys) leave the word
+            self.word.clear();
+            return match input.len() {
+                1 if self.mode != KeyEchoMode::None => Some("escape".to_string()),
+                _ => None,
+            };
+        }
+
+        let text = match std::str::from_utf8(input) {
+            Ok(text) => text,
+            Err(_) => {
+                self.word.clear();
+                return None;
+            }
+        };
+
+        let mut chars = text.chars();
+        let ch = match (chars.next(), chars.next()) {
+            (Some(ch), None) => ch,
+            (Some(_), Some(_)) => {
+                self.word.clear();
+                return self.pasted(text);
+            }
+            (None, _) => return None,
+        };
+
+        match ch {
+            '\r' | '\n' => {
+                self.word.clear();
+                None
+            }
+            '\x7f' | '\x08' => {
+                self.word.pop();
+                None
+            }
+            _ if ch.is_control() => {
+                self.word.clear();
+                if self.mode == KeyEchoMode::None {
+                    None
+                } else {
+                    control_key_name(ch)
+                }
+            }
+            _ => {
+                if !self.input_hidden {
+                    self.pending = Some(ch);
+                }
+                None
+            }
+        }
+    }
+
+    /// Check terminal output for the echo of the last typed character
+    ///
+    /// Returns None if the output isn't that echo, in which case it
+    /// should be spoken as normal output.
+    pub fn echoed(&mut self, output: &[u8]) -> Option<Echo> {
+        let ch = self.pending.take()?;
+        let mut encoded = [0u8; 4];
+        if output != ch.encode_utf8(&mut encoded).as_bytes() {
+            return None;
+        }
+
+        let mut echo = Echo::default();
+        if self.mode.characters() {
+            echo.character = Some(ch);
+        }
+        if is_word_char(ch) {
+            self.word.push(ch);
+        } else {
+            let word = std::mem::take(&mut self.word);
+            if self.mode.words() && !word.is_empty() {
+                echo.word = Some(word);
+            }
+        }
+        Some(echo)
+    }
+
+    /// Check whether output text is the echo of a paste
+    ///
+    /// Matching ignores whitespace, since shells redraw pasted lines
+    /// with different line endings and wrapping. Matched text is used up.
+    pub fn echoed_paste(&mut self, text: &str) -> bool {
+        let stripped: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+        if stripped.is_empty() || !self.paste_echo.starts_with(&stripped) {
+            return false;
+        }
+        self.paste_echo.drain(..stripped.len());
+        true
+    }
+
+    /// Summarize pasted text and expect its echo
+    fn pasted(&mut self, text: &str) -> Option<String> {
+        if self.mode == KeyEchoMode::None {
+            return None;
+        }
+        self.paste_echo = text.chars().filter(|c| !c.is_whitespace()).collect();
+        Some(paste_summary(text))
+    }
+}
+
+/// The printable character a key produces, if it is one
+pub fn typed_char(input: &[u8]) -> Option<char> {
+    let mut chars = std::str::from_utf8(input).ok()?.chars();
+    match (chars.next(), chars.next()) {
+        (Some(ch), None) if !ch.is_control() => Some(ch),
+        _ => None,
+    }
+}
+
+/// Extract the text of a bracketed paste
+fn bracketed_paste(input: &[u8]) -> Option<String> {
+    let body = input.strip_prefix(PASTE_START)?;
+    let body = body.strip_suffix(PASTE_END).unwrap_or(body);
+    Some(String::from_utf8_lossy(body).into_owned())
+}
+
+/// Describe pasted text by its size
+pub fn paste_summary(text: &str) -> String {
+    let lines = text
+        .split(['\r', '\n'])
+        .filter(|line| !line.is_empty())
+        .count();
+    if lines > 1 {
+        format!("pasted {} lines", lines)
+    } else {
+        format!("pasted {} characters", text.trim_end().chars().count())
+    }
+}
+
+/// Spoken name of a control key
+pub fn control_key_name(ch: char) -> Option<String> {
+    match ch {
+        '\t' => Some("tab".to_string()),
+        '\x00' => Some("control space".to_string()),
+        '\x01'..='\x1a' | '\x1c'..='\x1f' => Some(format!("control {}", (ch as u8 + b'@') as char)),
+        _ => None,
+    }
+}
+
+/// Check for characters that are part of a word
+fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_' || ch == '\''
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_and_echo(echo: &mut KeyEcho, text: &str) -> Vec<Echo> {
+        text.chars()
+            .map(|ch| {
+                let mut buf = [0u8; 4];
+                let bytes = ch.encode_utf8(&mut buf).as_bytes();
+                assert_eq!(echo.key_typed(bytes), None);
+                echo.echoed(bytes).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_mode_names() {
+        assert_eq!(KeyEchoMode::from_name("Words"), Some(KeyEchoMode::Words));
+        assert_eq!(
+            KeyEchoMode::from_name("true"),
+            Some(KeyEchoMode::Characters)
+        );
+        assert_eq!(KeyEchoMode::from_name("false"), Some(KeyEchoMode::None));
+        assert_eq!(KeyEchoMode::from_name("loud"), None);
+        assert_eq!(KeyEchoMode::Both.next(), KeyEchoMode::None);
+    }
+
+    #[test]
+    fn test_characters_mode() {
+        let mut echo = KeyEcho::new(KeyEchoMode::Characters);
+        let echoes = type_and_echo(&mut echo, "ls ");
+        assert_eq!(echoes[0].character, Some('l'));
+        assert!(echoes.iter().all(|e| e.word.is_none()));
+    }
+
+    #[test]
+    fn test_words_mode() {
+        let mut echo = KeyEcho::new(KeyEchoMode::Words);
+        let echoes = type_and_echo(&mut echo, "git status.");
+        assert!(echoes.iter().all(|e| e.character.is_none()));
+        assert_eq!(echoes[3].word.as_deref(), Some("git"));
+        assert_eq!(echoes[10].word.as_deref(), Some("status"));
+    }
+
+    #[test]
+    fn test_both_mode_with_backspace() {
+        let mut echo = KeyEcho::new(KeyEchoMode::Both);
+        type_and_echo(&mut echo, "cax");
+        assert_eq!(echo.key_typed(b"\x7f"), None);
+        type_and_echo(&mut echo, "t");
+        let echoes = type_and_echo(&mut echo, " ");
+        assert_eq!(
+            echoes[0],
+            Echo {
+                character: Some(' '),
+                word: Some("cat".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_multibyte_input() {
+        let mut echo = KeyEcho::new(KeyEchoMode::Both);
+        let echoes = type_and_echo(&mut echo, "café ");
+        assert_eq!(echoes[3].character, Some('é'));
+        assert_eq!(echoes[4].word.as_deref(), Some("café"));
+    }
+
+    #[test]
+    fn test_no_echo_is_not_spoken() {
+        let mut echo = KeyEcho::new(KeyEchoMode::Characters);
+        assert_eq!(echo.key_typed(b"s"), None);
+        // Password prompt: the shell prints nothing, then other output
+        assert_eq!(echo.echoed(b"*"), None);
+        assert_eq!(echo.echoed(b"s"), None);
+    }
+
+    #[test]
+    fn test_hidden_input_is_never_spoken() {
+        let mut echo = KeyEcho::new(KeyEchoMode::Both);
+        type_and_echo(&mut echo, "sudo");
+
+        assert!(echo.set_input_hidden(true));
+        assert!(!echo.set_input_hidden(true));
+        assert_eq!(echo.key_typed(b"s"), None);
+        assert_eq!(echo.echoed(b"s"), None);
+
+        assert!(!echo.set_input_hidden(false));
+        let echoes = type_and_echo(&mut echo, "ls ");
+        assert_eq!(echoes[2].word.as_deref(), Some("ls"));
+    }
+
+    #[test]
+    fn test_typed_char() {
+        assert_eq!(typed_char(b"a"), Some('a'));
+        assert_eq!(typed_char("é".as_bytes()), Some('é'));
+        assert_eq!(typed_char(b"\r"), None);
+        assert_eq!(typed_char(b"ab"), None);
+        assert_eq!(PasswordEcho::from_name("Bell"), Some(PasswordEcho::Bell));
+    }
+
+    #[test]
+    fn test_control_keys() {
+        let mut echo = KeyEcho::new(KeyEchoMode::Characters);
+        assert_eq!(echo.key_typed(b"\x03").as_deref(), Some("control C"));
+        assert_eq!(echo.key_typed(b"\t").as_deref(), Some("tab"));
+        assert_eq!(echo.key_typed(b"\x1b").as_deref(), Some("escape"));
+        assert_eq!(echo.key_typed(b"\x1b[A"), None);
+        assert_eq!(echo.key_typed(b"\r"), None);
+
+        let mut silent = KeyEcho::new(KeyEchoMode::None);
+        assert_eq!(silent.key_typed(b"\x03"), None);
+    }
+
+    #[test]
+    fn test_paste_is_summarized_and_echo_suppressed() {
+        let mut echo = KeyEcho::new(KeyEchoMode::Characters);
+        assert_eq!(
+            echo.key_typed(b"echo hello world").as_deref(),
+            Some("pasted 16 characters")
+        );
+        assert!(echo.echoed_paste("echo hello"));
+        assert!(echo.echoed_paste(" world"));
+        assert!(!echo.echoed_paste("hello world"));
+
+        assert_eq!(
+            echo.key_typed(b"\x1b[200~make\nmake install\n\x1b[201~")
+                .as_deref(),
+            Some("pasted 2 lines")
+        );
+        assert!(echo.echoed_paste("make"));
+        assert!(!echo.echoed_paste("gcc -o main main.c"));
+    }
+}
@@ -2,7 +2,17 @@
 
 pub mod backends;
 pub mod buffer;
+pub mod dictionary;
+pub mod flood;
+pub mod history;
+pub mod key_echo;
 pub mod synth;
+pub mod tone;
 
 pub use buffer::SpeechBuffer;
+pub use dictionary::Dictionary;
+pub use flood::FloodControl;
+pub use history::SpeechHistory;
+pub use key_echo::{KeyEcho, KeyEchoMode, PasswordEcho};
 pub use synth::{create_synth, SpeechCommand, Synth};
+pub use tone::TonePlayer;
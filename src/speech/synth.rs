@@ -51,6 +51,12 @@ pub trait Synth: Send {
 
     /// Cancel/silence current speech
     fn cancel(&mut self) -> Result<()>;
+
+    /// Check whether speech is still playing
+    ///
+    /// Used to pace continuous reading. Returns None if the backend
+    /// can't tell, in which case callers estimate speaking time instead.
+    fn is_speaking(&mut self) -> Option<bool>;
 }
 
 /// Create a platform-appropriate speech synthesizer
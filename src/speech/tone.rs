@@ -0,0 +1,201 @@
+//! Short audio tones
+//!
+//! Used for non-speech cues such as indentation. Tones are written as
+//! small WAV files and played with whichever system player is installed
+//! (paplay, aplay or afplay), so no audio library is needed.
+
+use crate::{Result, TdsrError};
+use log::{debug, info};
+use std::fs::{DirBuilder, OpenOptions};
+use std::io::{self, Write};
+use std::os::unix::fs::DirBuilderExt;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::time::Duration;
+
+/// Sample rate of generated tones
+pub const SAMPLE_RATE: u32 = 22050;
+
+/// Players tried in order, with their arguments before the file name
+const PLAYERS: &[(&str, &[&str])] = &[("paplay", &[]), ("aplay", &["-q"]), ("afplay", &[])];
+
+/// Build a mono 16-bit WAV file containing a sine tone
+///
+/// The start and end are faded to avoid clicks.
+pub fn wav_data(frequency: f32, duration: Duration, volume: f32) -> Vec<u8> {
+    let samples = (SAMPLE_RATE as f32 * duration.as_secs_f32()) as u32;
+    let fade = (samples / 10).max(1);
+    let data_len = samples * 2;
+
+    let mut wav = Vec::with_capacity(44 + data_len as usize);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+    wav.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    wav.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes());
+    wav.extend_from_slice(&2u16.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+
+    let amplitude = volume.clamp(0.0, 1.0) * i16::MAX as f32;
+    for i in 0..samples {
+        let t = i as f32 / SAMPLE_RATE as f32;
+        let envelope = (i.min(samples - i) as f32 / fade as f32).min(1.0);
+        let sample = (t * frequency * std::f32::consts::TAU).sin() * amplitude * envelope;
+        wav.extend_from_slice(&(sample as i16).to_le_bytes());
+    }
+    wav
+}
+
+/// Per-user directory tone files are cached in (~/.cache/tdsr/tones)
+///
+/// Created private to the user, so nobody else can plant or redirect
+/// the files played.
+fn tone_dir() -> Result<PathBuf> {
+    let dir = dirs::cache_dir()
+        .ok_or_else(|| TdsrError::Speech("No cache directory for tones".to_string()))?
+        .join("tdsr")
+        .join("tones");
+    DirBuilder::new().recursive(true).mode(0o700).create(&dir)?;
+    Ok(dir)
+}
+
+/// Get the WAV file for a tone in `dir`, writing it the first time
+///
+/// Tones are cached by frequency so repeated cues don't rewrite files.
+/// New files are created exclusively, never written through an existing
+/// file or symlink.
+pub fn tone_file(dir: &Path, frequency: f32, duration: Duration) -> Result<PathBuf> {
+    let path = dir.join(format!(
+        "tone-{}-{}.wav",
+        frequency.round() as u32,
+        duration.as_millis()
+    ));
+    match OpenOptions::new().write(true).create_new(true).open(&path) {
+        Ok(mut file) => file.write_all(&wav_data(frequency, duration, 0.3))?,
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+        Err(e) => return Err(e.into()),
+    }
+    Ok(path)
+}
+
+/// Find an executable on PATH
+fn find_in_path(name: &str) -> Option<PathBuf> {
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path)
+        .map(|dir| dir.join(name))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Plays tones through a system audio player
+pub struct TonePlayer {
+    /// Player program and its arguments
+    player: Option<(PathBuf, &'static [&'static str])>,
+
+    /// Tone currently playing
+    current: Option<Child>,
+}
+
+impl TonePlayer {
+    /// Find an audio player
+    pub fn new() -> Self {
+        let player = PLAYERS
+            .iter()
+            .find_map(|(name, args)| find_in_path(name).map(|path| (path, *args)));
+        match &player {
+            Some((path, _)) => info!("Playing tones with {:?}", path),
+            None => info!("No audio player found, tones unavailable"),
+        }
+        Self {
+            player,
+            current: None,
+        }
+    }
+
+    /// Check if tones can be played
+    pub fn is_available(&self) -> bool {
+        self.player.is_some()
+    }
+
+    /// Play a tone, cutting off any tone still playing
+    pub fn play(&mut self, frequency: f32, duration: Duration) -> Result<()> {
+        let (player, args) = match &self.player {
+            Some(player) => player,
+            None => return Err(TdsrError::Speech("No audio player for tones".to_string())),
+        };
+
+        let path = tone_file(&tone_dir()?, frequency, duration)?;
+
+        if let Some(mut child) = self.current.take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+
+        debug!("Playing {} Hz tone", frequency);
+        let child = Command::new(player)
+            .args(*args)
+            .arg(&path)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()?;
+        self.current = Some(child);
+        Ok(())
+    }
+}
+
+impl Default for TonePlayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for TonePlayer {
+    fn drop(&mut self) {
+        if let Some(mut child) = self.current.take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wav_header_and_length() {
+        let wav = wav_data(440.0, Duration::from_millis(100), 0.5);
+        let samples = SAMPLE_RATE / 10;
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(&wav[8..16], b"WAVEfmt ");
+        assert_eq!(&wav[36..40], b"data");
+        assert_eq!(
+            u32::from_le_bytes(wav[40..44].try_into().unwrap()),
+            samples * 2
+        );
+        assert_eq!(wav.len(), 44 + samples as usize * 2);
+        // Faded in from silence
+        assert_eq!(i16::from_le_bytes([wav[44], wav[45]]), 0);
+    }
+
+    #[test]
+    fn test_tone_file_written_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let duration = Duration::from_millis(50);
+
+        let path = tone_file(dir.path(), 440.4, duration).unwrap();
+        assert_eq!(path, dir.path().join("tone-440-50.wav"));
+        let written = std::fs::read(&path).unwrap();
+        assert_eq!(written, wav_data(440.4, duration, 0.3));
+
+        // An existing file is reused rather than rewritten
+        std::fs::write(&path, b"cached").unwrap();
+        assert_eq!(tone_file(dir.path(), 440.0, duration).unwrap(), path);
+        assert_eq!(std::fs::read(&path).unwrap(), b"cached");
+    }
+}
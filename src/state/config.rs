@@ -1,5 +1,10 @@
 //! Configuration management
 
+use crate::review::indentation::IndentationMode;
+use crate::review::regions::Region;
+use crate::review::words::WordRules;
+use crate::speech::{KeyEchoMode, PasswordEcho};
+use crate::symbols::{self, SymbolLevel};
 use crate::{Result, TdsrError};
 use ini::Ini;
 use log::{debug, info};
@@ -22,6 +27,10 @@ pub struct Config {
     /// e.g., 33 -> "bang" so '!' is spoken as "bang" not just "exclamation"
     pub symbols: HashMap<u32, String>,
 
+    /// Symbol verbosity levels (char code -> level)
+    /// A symbol is spoken in text when the current symbol level is at or above its level
+    pub symbol_levels: HashMap<u32, SymbolLevel>,
+
     /// Cached compiled regex for efficient symbol replacement
     /// Built from symbols dictionary, compiled once on config load
     symbols_regex: Option<Regex>,
@@ -53,10 +62,22 @@ impl Config {
             default
         };
 
+        Ok(Self::from_ini(ini, path))
+    }
+
+    /// Default configuration, never saved, for tests
+    #[cfg(test)]
+    pub(crate) fn defaults() -> Self {
+        Self::from_ini(Self::default_config(), PathBuf::new())
+    }
+
+    /// Build the configuration from parsed INI settings
+    fn from_ini(ini: Ini, path: PathBuf) -> Self {
         let mut config = Self {
             ini,
             path,
             symbols: HashMap::new(),
+            symbol_levels: HashMap::new(),
             symbols_regex: None,
             plugins: HashMap::new(),
             plugin_commands: HashMap::new(),
@@ -66,7 +87,7 @@ impl Config {
         config.parse_plugins();
         config.build_symbols_regex();
 
-        Ok(config)
+        config
     }
 
     /// Save configuration to disk
@@ -85,6 +106,12 @@ impl Config {
         PathBuf::from(home).join(".tdsr.cfg")
     }
 
+    /// Get pronunciation dictionary path (~/.tdsr/dictionary)
+    pub fn dictionary_path(&self) -> PathBuf {
+        let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+        PathBuf::from(home).join(".tdsr").join("dictionary")
+    }
+
     /// Expose the config file path for display
     pub fn path(&self) -> &PathBuf {
         &self.path
@@ -95,48 +122,38 @@ impl Config {
         let mut ini = Ini::new();
 
         ini.with_section(Some("speech"))
-            .set("process_symbols", "false")
-            .set("key_echo", "true")
+            .set("symbol_level", "none")
+            .set("key_echo", "characters")
+            .set("password_echo", "star")
             .set("cursor_tracking", "true")
+            .set("highlight_tracking", "false")
+            .set("word_separators", "")
+            .set("sub_words", "false")
             .set("line_pause", "true")
+            .set("indentation", "off")
+            .set("indentation_always", "false")
             .set("repeated_symbols", "false")
             .set("repeated_symbols_values", "-=!#")
+            .set("history_size", "100")
+            .set("scrollback_lines", "1000")
+            .set("search_ignore_case", "true")
+            .set("flood_lines", "100")
+            .set("flood_bytes", "8000")
+            .set("flood_settle", "500")
+            .set("coalesce_window", "50")
             .set("prompt", ".*");
 
-        ini.with_section(Some("symbols"))
-            .set("32", "space")
-            .set("33", "bang")
-            .set("34", "quote")
-            .set("35", "number")
-            .set("36", "dollar")
-            .set("37", "percent")
-            .set("38", "and")
-            .set("39", "tick")
-            .set("40", "left paren")
-            .set("41", "right paren")
-            .set("42", "star")
-            .set("43", "plus")
-            .set("44", "comma")
-            .set("45", "dash")
-            .set("46", "dot")
-            .set("47", "slash")
-            .set("58", "colon")
-            .set("59", "semi")
-            .set("60", "less")
-            .set("61", "equals")
-            .set("62", "greater")
-            .set("63", "question")
-            .set("64", "at")
-            .set("91", "left bracket")
-            .set("92", "backslash")
-            .set("93", "right bracket")
-            .set("94", "caret")
-            .set("95", "line")
-            .set("96", "grav")
-            .set("123", "left brace")
-            .set("124", "bar")
-            .set("125", "right brace")
-            .set("126", "tilda");
+        // Symbol names, each tagged with the level it is spoken at
+        for (code, name, level) in symbols::DEFAULT_SYMBOLS {
+            ini.with_section(Some("symbols"))
+                .set(code.to_string(), format!("{}, {}", name, level.name()));
+        }
+
+        ini.with_section(Some("braille")).set("enabled", "false");
+
+        ini.with_section(Some("clipboard"))
+            .set("backend", "auto")
+            .set("directory", "~/.tdsr/copies");
 
         ini.with_section(Some("commands"));
         ini.with_section(Some("plugins"));
@@ -149,7 +166,9 @@ impl Config {
         if let Some(section) = self.ini.section(Some("symbols")) {
             for (key, value) in section.iter() {
                 if let Ok(code) = key.parse::<u32>() {
-                    self.symbols.insert(code, value.to_string());
+                    let (name, level) = symbols::parse_symbol_entry(code, value);
+                    self.symbols.insert(code, name);
+                    self.symbol_levels.insert(code, level);
                 }
             }
         }
@@ -254,16 +273,42 @@ impl Config {
 
     // Screen reader-specific configuration getters
 
+    /// How much punctuation is spoken when reading text
+    /// Older configs only have the on/off process_symbols flag, which maps to all/none
+    pub fn symbol_level(&self) -> SymbolLevel {
+        self.ini
+            .get_from(Some("speech"), "symbol_level")
+            .and_then(SymbolLevel::from_name)
+            .unwrap_or_else(|| {
+                if self.get_bool("speech", "process_symbols", false) {
+                    SymbolLevel::All
+                } else {
+                    SymbolLevel::None
+                }
+            })
+    }
+
     /// Should the screen reader process symbols into words?
     /// When true, "!" becomes "bang", "$" becomes "dollar", etc.
     pub fn process_symbols(&self) -> bool {
-        self.get_bool("speech", "process_symbols", false)
+        self.symbol_level() != SymbolLevel::None
+    }
+
+    /// What is spoken while typing: none, characters, words or both
+    /// Older configs have an on/off flag, which maps to characters/none
+    pub fn key_echo(&self) -> KeyEchoMode {
+        self.ini
+            .get_from(Some("speech"), "key_echo")
+            .and_then(KeyEchoMode::from_name)
+            .unwrap_or(KeyEchoMode::Characters)
     }
 
-    /// Should individual keystrokes be echoed?
-    /// When true, typing "a" speaks "a"
-    pub fn key_echo(&self) -> bool {
-        self.get_bool("speech", "key_echo", true)
+    /// Feedback for keys typed at password prompts: star, bell or none
+    pub fn password_echo(&self) -> PasswordEcho {
+        self.ini
+            .get_from(Some("speech"), "password_echo")
+            .and_then(PasswordEcho::from_name)
+            .unwrap_or(PasswordEcho::Star)
     }
 
     /// Should cursor position be tracked and spoken?
@@ -272,12 +317,46 @@ impl Config {
         self.get_bool("speech", "cursor_tracking", true)
     }
 
+    /// Should the highlighted item in full-screen programs be spoken?
+    /// When true, a moved selection bar is read and the review cursor follows it
+    pub fn highlight_tracking(&self) -> bool {
+        self.get_bool("speech", "highlight_tracking", false)
+    }
+
+    /// How review word navigation splits lines into words
+    /// Separators are characters that end words besides whitespace
+    pub fn word_rules(&self) -> WordRules {
+        WordRules::new(
+            &self.get_string("speech", "word_separators", ""),
+            self.sub_words(),
+        )
+    }
+
+    /// Should word navigation stop at sub-words (path parts, snake_case, camelCase)?
+    pub fn sub_words(&self) -> bool {
+        self.get_bool("speech", "sub_words", false)
+    }
+
     /// Should speech pause at newlines?
     /// When true, each line is spoken separately as it arrives
     pub fn line_pause(&self) -> bool {
         self.get_bool("speech", "line_pause", true)
     }
 
+    /// How indentation is announced when reading lines: off, speak or tone
+    pub fn indentation(&self) -> IndentationMode {
+        self.ini
+            .get_from(Some("speech"), "indentation")
+            .and_then(IndentationMode::from_name)
+            .unwrap_or(IndentationMode::Off)
+    }
+
+    /// Should indentation be announced on every line?
+    /// When false, it is only announced when it changes
+    pub fn indentation_always(&self) -> bool {
+        self.get_bool("speech", "indentation_always", false)
+    }
+
     /// Should repeated symbols be condensed?
     /// When true, "====" becomes "4 equals" instead of "equals equals equals equals"
     pub fn repeated_symbols(&self) -> bool {
@@ -289,6 +368,56 @@ impl Config {
         self.get_string("speech", "repeated_symbols_values", "-=!#")
     }
 
+    /// How many spoken utterances to keep for history review
+    pub fn history_size(&self) -> usize {
+        self.get_int("speech", "history_size", 100)
+            .try_into()
+            .unwrap_or(0)
+    }
+
+    /// How many lines scrolled off the screen are kept for search and reading
+    pub fn scrollback_lines(&self) -> usize {
+        self.get_int("speech", "scrollback_lines", 1000)
+            .try_into()
+            .unwrap_or(0)
+    }
+
+    /// Should searches ignore case?
+    pub fn search_ignore_case(&self) -> bool {
+        self.get_bool("speech", "search_ignore_case", true)
+    }
+
+    /// Lines of output per second above which individual lines stop being spoken
+    /// 0 disables the line limit
+    pub fn flood_lines(&self) -> usize {
+        self.get_int("speech", "flood_lines", 100)
+            .try_into()
+            .unwrap_or(0)
+    }
+
+    /// Bytes of output per second above which individual lines stop being spoken
+    /// 0 disables the byte limit
+    pub fn flood_bytes(&self) -> usize {
+        self.get_int("speech", "flood_bytes", 8000)
+            .try_into()
+            .unwrap_or(0)
+    }
+
+    /// Milliseconds of quiet after which a flood is over and speech resumes
+    pub fn flood_settle(&self) -> u64 {
+        self.get_int("speech", "flood_settle", 500)
+            .try_into()
+            .unwrap_or(0)
+    }
+
+    /// Milliseconds to wait for more output before speaking a partial line
+    /// Merges output split across reads into one utterance; 0 disables
+    pub fn coalesce_window(&self) -> u64 {
+        self.get_int("speech", "coalesce_window", 50)
+            .try_into()
+            .unwrap_or(0)
+    }
+
     /// Speech rate (0-100)
     pub fn rate(&self) -> Option<u8> {
         self.get_int("speech", "rate", -1)
@@ -324,4 +453,99 @@ impl Config {
         let ms = self.get_float("speech", "cursor_delay", 20.0);
         ms / 1000.0
     }
+
+    /// Directory that copy mode saves files to
+    pub fn copy_directory(&self) -> PathBuf {
+        expand_home(&self.get_string("clipboard", "directory", "~/.tdsr/copies"))
+    }
+
+    /// Clipboard backend: auto, arboard, osc52, tmux, file or command
+    pub fn clipboard_backend(&self) -> String {
+        self.get_string("clipboard", "backend", "auto")
+    }
+
+    /// File the `file` clipboard backend keeps its content in
+    pub fn clipboard_file(&self) -> PathBuf {
+        expand_home(&self.get_string("clipboard", "file", "~/.tdsr/clipboard"))
+    }
+
+    /// Shell command the `command` clipboard backend pipes copied text to
+    pub fn clipboard_command(&self) -> String {
+        self.get_string("clipboard", "command", "")
+    }
+
+    /// Should output also be shown on a braille display through BrlAPI?
+    pub fn braille_enabled(&self) -> bool {
+        self.get_bool("braille", "enabled", false)
+    }
+
+    /// Path of the BrlAPI socket to connect to
+    /// Defaults to brltty's first local socket
+    pub fn braille_socket(&self) -> PathBuf {
+        let default = format!("{}/0", crate::braille::brlapi::SOCKET_DIR);
+        PathBuf::from(self.get_string("braille", "socket", &default))
+    }
+
+    /// Path of the BrlAPI authentication key
+    pub fn braille_key_file(&self) -> PathBuf {
+        PathBuf::from(self.get_string(
+            "braille",
+            "key_file",
+            crate::braille::brlapi::DEFAULT_KEY_FILE,
+        ))
+    }
+
+    /// Screen regions from `[region.<name>]` sections
+    ///
+    /// Each section may set `top`, `bottom`, `left` and `right` edges
+    /// (1-based, negative from the bottom or right), a `key` to read the
+    /// region with alt, and `watch` to announce changes.
+    pub fn regions(&self) -> Vec<Region> {
+        self.ini
+            .iter()
+            .filter_map(|(section, props)| {
+                let name = section?.strip_prefix("region.")?;
+                let mut region = Region::new(name);
+                let edge = |key: &str, default: i32| {
+                    props
+                        .get(key)
+                        .and_then(|v| v.trim().parse().ok())
+                        .unwrap_or(default)
+                };
+                region.top = edge("top", region.top);
+                region.bottom = edge("bottom", region.bottom);
+                region.left = edge("left", region.left);
+                region.right = edge("right", region.right);
+                region.key = props.get("key").and_then(|key| {
+                    let mut chars = key.trim().chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(ch), None) => Some(ch),
+                        _ => None,
+                    }
+                });
+                region.watch = props
+                    .get("watch")
+                    .and_then(|v| v.trim().parse().ok())
+                    .unwrap_or(false);
+                Some(region)
+            })
+            .collect()
+    }
+
+    /// Virtual terminal to claim the display for
+    /// Detected from the environment when not set
+    pub fn braille_tty(&self) -> Option<u32> {
+        self.get_int("braille", "tty", -1).try_into().ok()
+    }
+}
+
+/// Expand a leading ~ in a configured path to the home directory
+fn expand_home(path: &str) -> PathBuf {
+    match path.strip_prefix('~') {
+        Some(rest) => {
+            let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+            PathBuf::from(home).join(rest.trim_start_matches('/'))
+        }
+        None => PathBuf::from(path),
+    }
 }
@@ -5,19 +5,45 @@
 
 pub mod config;
 pub mod phonetics;
+#[cfg(test)]
+pub(crate) mod testing;
 
-use crate::input::HandlerStack;
+use crate::braille::{self, BrailleDisplay};
+use crate::clipboard::{self, ClipboardBackend};
+use crate::input::default_handler::{DefaultKeyHandler, DOUBLE_TAP_WINDOW};
+use crate::input::{routing, HandlerStack};
 use crate::plugins::PluginManager;
-use crate::review::ReviewCursor;
-use crate::speech::{SpeechBuffer, Synth};
-use crate::terminal::Screen;
+use crate::review::blocks::{self, Block};
+use crate::review::bookmarks::{Bookmark, Bookmarks};
+use crate::review::highlight::{self, HighlightTracker};
+use crate::review::indentation::{self, IndentationMode, IndentationTracker};
+use crate::review::output;
+use crate::review::regions::Regions;
+use crate::review::say_all::{self, SayAll};
+use crate::review::search::{Direction, Match, Search};
+use crate::review::selection::Selection;
+use crate::review::table::Table;
+use crate::review::{self, ReviewCursor};
+use crate::speech::dictionary::{self, DictionaryEntry};
+use crate::speech::{
+    key_echo, Dictionary, FloodControl, KeyEcho, PasswordEcho, SpeechBuffer, SpeechHistory, Synth,
+    TonePlayer,
+};
+use crate::terminal::{Emulator, Screen};
 use crate::Result;
 use config::Config;
-use log::info;
+use log::{debug, info};
 use phonetics::PHONETICS;
+use std::io::Write;
 use std::time::{Duration, Instant};
 use unicode_width::UnicodeWidthChar;
 
+/// Quiet time after output before looking for a moved highlight
+const HIGHLIGHT_SETTLE: Duration = Duration::from_millis(100);
+
+/// Shortest wait before checking where a routed cursor ended up
+const ROUTE_CHECK_DELAY: Duration = Duration::from_millis(150);
+
 /// Type for delayed functions (used for cursor tracking)
 /// Stores a function to call and when it should be called
 type DelayedFunction = (
@@ -38,10 +64,17 @@ pub struct State {
     /// to read any part of the screen
     pub review: ReviewCursor,
 
+    /// Column kept by vertical review movement, with the position it
+    /// landed on; the column is reused while the review cursor stays there
+    vertical_column: Option<(u16, (u16, u16))>,
+
     /// Speech synthesizer for text-to-speech output
     /// This is how the screen reader speaks to the user
     pub synth: Box<dyn Synth>,
 
+    /// Where copied text goes (system clipboard, OSC 52, tmux, ...)
+    pub clipboard: Box<dyn ClipboardBackend>,
+
     /// Last position where text was drawn to screen
     /// Used to track what's new for automatic speech
     pub last_drawn: (u16, u16),
@@ -58,13 +91,32 @@ pub struct State {
     /// Text is added as it's drawn, then flushed to TTS
     pub speech_buffer: SpeechBuffer,
 
+    /// Recently spoken utterances
+    /// Lets the user step back through speech they missed during output bursts
+    pub history: SpeechHistory,
+
+    /// Flood control for large output bursts
+    /// Stops speaking individual lines when output arrives too fast to follow
+    pub flood: FloodControl,
+
+    /// Pronunciation dictionary applied before speaking
+    /// Rewrites words and patterns such as command names and hashes
+    pub dictionary: Dictionary,
+
     /// Key handler stack for modal input
     /// Allows config menu, copy mode, etc. to intercept keys
     pub handlers: HandlerStack,
 
-    /// Copy/selection start position if user is selecting text
-    /// Used with alt+r to mark selection start
-    pub copy_start: Option<(u16, u16)>,
+    /// Selection in progress, if the user is selecting text
+    /// Started with alt+r; alt+r twice starts a block selection
+    pub selection: Option<Selection>,
+
+    /// When to copy the selection, and where it ends; the copy waits out
+    /// the double-tap window so a second alt+r can switch modes instead
+    selection_copy: Option<(Instant, (u16, u16))>,
+
+    /// Speech collected to be sent as one utterance, while held
+    held_speech: Option<Vec<String>>,
 
     /// Flag indicating delayed speech is pending
     /// Used for cursor tracking - speech happens after a short delay
@@ -74,15 +126,53 @@ pub struct State {
     /// Some plugins only trigger after specific commands
     pub last_command: String,
 
-    /// Last key typed by user (for key echo)
-    /// When terminal echoes this character back and key_echo is enabled,
-    /// we speak the character
-    pub last_key: Option<char>,
+    /// Continuous reading in progress
+    pub say_all: Option<SayAll>,
+
+    /// Named review positions
+    pub bookmarks: Bookmarks,
+
+    /// Configured screen regions
+    pub regions: Regions,
+
+    /// Highlighted text seen in full-screen programs
+    highlight: HighlightTracker,
+
+    /// When to look for a moved highlight, once output has settled
+    highlight_due: Option<Instant>,
+
+    /// Current search, repeated by find next / find previous
+    pub search: Option<Search>,
+
+    /// Where the last search match was, as a fixed line number (see
+    /// `Screen::first_line_number`) and column, so it stays with its line
+    /// as old scrollback lines are dropped
+    pub last_match: Option<(u64, usize)>,
+
+    /// Indentation of the last line read, for announcing changes
+    pub indentation: IndentationTracker,
+
+    /// Player for non-speech tones
+    pub tones: TonePlayer,
+
+    /// Process ID of the shell, for finding its working directory
+    pub shell_pid: Option<u32>,
+
+    /// Keys to send to the shell, written after the current key is handled
+    pending_input: Vec<u8>,
+
+    /// Key echo tracking
+    /// Matches typed keys against the terminal's echo to decide what to speak
+    pub key_echo: KeyEcho,
 
     /// Plugin manager for executing external plugins
     /// Allows custom output parsing and speech generation
     pub plugin_manager: Option<PluginManager>,
 
+    /// Braille display showing the line under the review cursor
+    /// None when braille is disabled or brltty isn't reachable
+    pub braille: Option<BrailleDisplay>,
+
     /// Delayed functions for cursor tracking
     /// Functions scheduled to run after a delay (e.g., speak character after arrow key)
     delayed_functions: Vec<DelayedFunction>,
@@ -99,8 +189,8 @@ impl State {
         info!("Configuration loaded from {:?}", config.path());
         info!("  Symbols: {}", config.symbols.len());
         info!("  Plugins: {}", config.plugins.len());
-        info!("  Process symbols: {}", config.process_symbols());
-        info!("  Key echo: {}", config.key_echo());
+        info!("  Symbol level: {}", config.symbol_level().name());
+        info!("  Key echo: {}", config.key_echo().name());
         info!("  Cursor tracking: {}", config.cursor_tracking());
 
         // Create speech synthesizer
@@ -121,6 +211,18 @@ impl State {
             info!("Speech voice index set to {}", voice_idx);
         }
 
+        Self::with_synth(config, synth, cols, rows)
+    }
+
+    /// Create state around a loaded configuration and synthesizer
+    pub(crate) fn with_synth(
+        config: Config,
+        synth: Box<dyn Synth>,
+        cols: u16,
+        rows: u16,
+    ) -> Result<Self> {
+        let regions = Regions::new(config.regions());
+
         // Initialize plugin manager if plugins are configured
         let plugin_manager = if !config.plugins.is_empty() {
             let plugin_dir = dirs::home_dir()
@@ -153,20 +255,80 @@ impl State {
             None
         };
 
+        // Load the pronunciation dictionary
+        let dictionary = match Dictionary::load(&config.dictionary_path()) {
+            Ok(dictionary) => dictionary,
+            Err(e) => {
+                info!("Failed to load dictionary: {}", e);
+                Dictionary::new()
+            }
+        };
+
+        // Connect to the braille display if enabled
+        let braille = if config.braille_enabled() {
+            match BrailleDisplay::connect(
+                &config.braille_socket(),
+                &config.braille_key_file(),
+                config.braille_tty(),
+            ) {
+                Ok(display) => {
+                    info!("Braille display connected, {} cells", display.width());
+                    Some(display)
+                }
+                Err(e) => {
+                    info!("Failed to connect to braille display: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let mut speech_buffer = SpeechBuffer::new();
+        speech_buffer.set_window(Duration::from_millis(config.coalesce_window()));
+        let key_echo = KeyEcho::new(config.key_echo());
+        let history_size = config.history_size();
+        let flood = FloodControl::new(
+            config.flood_lines(),
+            config.flood_bytes(),
+            Duration::from_millis(config.flood_settle()),
+        );
+
+        let clipboard = clipboard::create_backend(&config);
+
         Ok(Self {
             config,
             review: ReviewCursor::new(cols, rows),
+            vertical_column: None,
             synth,
+            clipboard,
             last_drawn: (0, 0),
             quiet: false,
             temp_silence: false,
-            speech_buffer: SpeechBuffer::new(),
+            speech_buffer,
+            history: SpeechHistory::new(history_size),
+            flood,
+            dictionary,
             handlers: HandlerStack::new(),
-            copy_start: None,
+            selection: None,
+            selection_copy: None,
+            held_speech: None,
             delaying_output: false,
             last_command: String::new(),
-            last_key: None,
+            key_echo,
+            shell_pid: None,
+            pending_input: Vec::new(),
+            say_all: None,
+            bookmarks: Bookmarks::new(),
+            regions,
+            highlight: HighlightTracker::new(),
+            highlight_due: None,
+            search: None,
+            last_match: None,
+            indentation: IndentationTracker::new(),
+            tones: TonePlayer::new(),
             plugin_manager,
+            braille,
             delayed_functions: Vec::new(),
         })
     }
@@ -206,100 +368,226 @@ impl State {
     ///
     /// Marks current review cursor position as selection start
     pub fn start_selection(&mut self) {
-        self.copy_start = Some(self.review.pos);
+        self.selection = Some(Selection::new(self.review.pos));
     }
 
-    /// End text selection and copy to clipboard
+    /// Switch the selection between linear and block mode
     ///
-    /// Copies the selected region from copy_start to current review cursor position
-    pub fn copy_selection(&mut self, screen: &Screen) -> Result<()> {
-        if let Some((start_x, start_y)) = self.copy_start {
-            let (end_x, end_y) = self.review.pos;
-
-            // Copy text from selection
-            let text = self.copy_text_range(screen, start_x, start_y, end_x, end_y);
+    /// Starts a selection first if none is in progress.
+    pub fn toggle_selection_mode(&mut self) -> Result<()> {
+        // The first press of the double tap asked to copy; keep selecting instead
+        self.selection_copy = None;
+        let pos = self.review.pos;
+        let selection = self.selection.get_or_insert_with(|| Selection::new(pos));
+        selection.block = !selection.block;
+        let msg = if selection.block {
+            "block select"
+        } else {
+            "linear select"
+        };
+        self.speak(msg)
+    }
 
-            // Copy to clipboard
-            crate::clipboard::copy_to_clipboard(&text)?;
+    /// Size of the selection, if one is in progress and has moved off its start
+    pub fn selection_extent(&self) -> Option<String> {
+        self.selection
+            .filter(|selection| selection.start != self.review.pos)
+            .map(|selection| selection.extent(self.review.pos))
+    }
 
-            // Clear selection
-            self.copy_start = None;
+    /// End text selection and copy it once the double-tap window passes
+    ///
+    /// The selection ends at the current review cursor position.
+    pub fn request_selection_copy(&mut self) {
+        self.selection_copy = Some((Instant::now() + DOUBLE_TAP_WINDOW, self.review.pos));
+    }
 
-            self.speak("copied")?;
+    /// Copy the selection if a requested copy is due
+    pub fn check_selection_copy(&mut self, screen: &Screen) -> Result<()> {
+        match self.selection_copy {
+            Some((due, end)) if Instant::now() >= due => {
+                self.selection_copy = None;
+                self.copy_selection(screen, end)
+            }
+            _ => Ok(()),
         }
-        Ok(())
     }
 
-    /// Copy text from a linear region of the screen
+    /// End text selection and copy to clipboard
     ///
-    /// Performs a linear (stream) selection from start to end position,
-    /// like selecting text in a word processor. Multi-line selections
-    /// include the end of the first line, full middle lines, and the
-    /// beginning of the last line.
-    fn copy_text_range(
-        &self,
-        screen: &Screen,
-        mut start_x: u16,
-        mut start_y: u16,
-        mut end_x: u16,
-        mut end_y: u16,
-    ) -> String {
-        // Normalize so start is before end in reading order (row-major)
-        // Important: swap both x and y together to maintain the linear selection
-        if start_y > end_y || (start_y == end_y && start_x > end_x) {
-            std::mem::swap(&mut start_x, &mut end_x);
-            std::mem::swap(&mut start_y, &mut end_y);
-        }
-
-        let mut text = String::new();
-        let (cols, _) = screen.size;
-
-        for y in start_y..=end_y {
-            // On first row: start from start_x
-            // On subsequent rows: start from column 0
-            let line_start = if y == start_y { start_x } else { 0 };
-
-            // On last row: end at end_x
-            // On earlier rows: end at last column
-            let line_end = if y == end_y { end_x } else { cols - 1 };
-
-            // Get characters from this line
-            for x in line_start..=line_end {
-                if let Some(ch) = screen.get_char(x, y) {
-                    // Skip wide character continuation cells
-                    if ch != '\0' {
-                        text.push(ch);
-                    }
-                }
-            }
-
-            // Add newline except for last line
-            if y < end_y {
-                text.push('\n');
+    /// Copies the selected region from the selection start to `end`
+    fn copy_selection(&mut self, screen: &Screen, end: (u16, u16)) -> Result<()> {
+        if let Some(selection) = self.selection.take() {
+            let text = selection.text(screen, end);
+            match self.clipboard.copy(&text) {
+                Ok(()) => self.speak("copied")?,
+                Err(e) => self.speak(&format!("copy failed, {}", e))?,
             }
         }
-
-        text
+        Ok(())
     }
 
     /// End text selection without copying
     pub fn end_selection(&mut self) {
-        self.copy_start = None;
+        self.selection = None;
+        self.selection_copy = None;
     }
 
     /// Check if selection is active
     pub fn has_selection(&self) -> bool {
-        self.copy_start.is_some()
+        self.selection.is_some()
     }
 
     /// Speak text to the user
     ///
     /// Central method for all screen reader speech output
-    /// Processes symbols if enabled (e.g., "!" becomes "bang")
+    /// Applies the pronunciation dictionary, processes symbols if enabled
+    /// (e.g., "!" becomes "bang") and records the utterance in the speech history
     pub fn speak(&mut self, text: &str) -> Result<()> {
         if !self.quiet {
-            let processed = self.process_symbols_in_text(text);
-            self.synth.speak(&processed)?;
+            self.history.push(text);
+        }
+        self.speak_unrecorded(text)
+    }
+
+    /// Speak text without recording it in the speech history
+    ///
+    /// Used when reading back history entries so that reviewing
+    /// history doesn't change it
+    fn speak_unrecorded(&mut self, text: &str) -> Result<()> {
+        if !self.quiet {
+            let pronounced = self.dictionary.apply(text);
+            let processed = self.process_symbols_in_text(&pronounced);
+            match self.held_speech.as_mut() {
+                Some(held) => held.push(processed),
+                None => self.synth.speak(&processed)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Speak a single letter or character name
+    fn letter(&mut self, text: &str) -> Result<()> {
+        match self.held_speech.as_mut() {
+            Some(held) => {
+                held.push(text.to_string());
+                Ok(())
+            }
+            None => self.synth.letter(text),
+        }
+    }
+
+    /// Collect speech instead of speaking it, until `release_speech`
+    ///
+    /// Each new utterance cancels the last on some backends, so speech
+    /// that belongs together is held and sent as one.
+    pub fn hold_speech(&mut self) {
+        self.held_speech = Some(Vec::new());
+    }
+
+    /// Speak the held speech as one utterance, followed by `extra`
+    ///
+    /// `extra` is not recorded in the speech history.
+    pub fn release_speech(&mut self, extra: Option<&str>) -> Result<()> {
+        let mut held = self.held_speech.take().unwrap_or_default();
+        if let Some(extra) = extra {
+            if !self.quiet {
+                held.push(self.process_symbols_in_text(&self.dictionary.apply(extra)));
+            }
+        }
+        if held.is_empty() {
+            return Ok(());
+        }
+        self.synth.speak(&held.join(", "))
+    }
+
+    /// Speak text even in quiet mode
+    ///
+    /// Used for watched regions, which are meant to be heard while the
+    /// rest of the output is silenced.
+    fn speak_despite_quiet(&mut self, text: &str) -> Result<()> {
+        self.history.push(text);
+        let pronounced = self.dictionary.apply(text);
+        let processed = self.process_symbols_in_text(&pronounced);
+        self.synth.speak(&processed)
+    }
+
+    /// Speak new terminal output, subject to flood control
+    ///
+    /// During a flood the output is not spoken but is still recorded in
+    /// the speech history, so it can be reviewed afterwards.
+    pub fn speak_output(&mut self, text: &str) -> Result<()> {
+        // Pasted text was already summarized when it was typed
+        if self.key_echo.echoed_paste(text) {
+            return Ok(());
+        }
+
+        let was_flooding = self.flood.is_flooding();
+        if self.flood.observe(text, Instant::now()) {
+            return self.speak(text);
+        }
+
+        if !was_flooding {
+            info!("Output flood started, skipping speech");
+            // Drop queued lines so speech catches up with the screen
+            self.cancel_speech()?;
+        }
+        if !self.quiet {
+            self.history.push(text);
+        }
+        Ok(())
+    }
+
+    /// Speak held output once its coalescing window has passed
+    pub fn speak_held_output(&mut self) -> Result<()> {
+        if self.speech_buffer.is_due(Instant::now()) {
+            let text = self.speech_buffer.flush();
+            if !self.quiet && !self.temp_silence {
+                self.speak_output(&text)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Announce how much output was skipped once a flood settles
+    pub fn check_flood_settled(&mut self) -> Result<()> {
+        if let Some(lines) = self.flood.check_settled(Instant::now()) {
+            info!("Output flood settled after {} lines", lines);
+            let summary = if lines == 1 {
+                "1 line".to_string()
+            } else {
+                format!("{} lines", lines)
+            };
+            self.speak(&summary)?;
+        }
+        Ok(())
+    }
+
+    /// Record a key being passed through to the shell
+    ///
+    /// Control keys and pastes are spoken now; typed characters are
+    /// spoken when the terminal echoes them. While the terminal hides
+    /// input (a password prompt), keys only get neutral feedback.
+    pub fn key_typed(&mut self, input: &[u8], input_hidden: bool) -> Result<()> {
+        if self.key_echo.set_input_hidden(input_hidden) {
+            info!("Terminal echo off, treating input as a password");
+            self.speak("password")?;
+        }
+        if input_hidden && key_echo::typed_char(input).is_some() {
+            match self.config.password_echo() {
+                PasswordEcho::Star => self.speak("star")?,
+                PasswordEcho::Bell => {
+                    let mut stdout = std::io::stdout();
+                    stdout.write_all(b"\x07")?;
+                    stdout.flush()?;
+                }
+                PasswordEcho::None => {}
+            }
+            return Ok(());
+        }
+
+        if let Some(text) = self.key_echo.key_typed(input) {
+            self.speak(&text)?;
         }
         Ok(())
     }
@@ -314,48 +602,57 @@ impl State {
         }
 
         // For special characters, use their symbol name
-        if let Some(name) = self.config.symbols.get(&(ch as u32)) {
-            self.synth.letter(name)?;
+        if let Some(name) = self.char_name(ch) {
+            self.letter(&name)?;
         } else {
             // Use letter mode for regular characters
-            self.synth.letter(&ch.to_string())?;
+            self.letter(&ch.to_string())?;
         }
         Ok(())
     }
 
-    /// Process symbols in text if enabled
+    /// Process symbols in text according to the symbol level
     ///
     /// Converts special characters to their word equivalents
-    /// (e.g., "!" → "bang", "$" → "dollar")
+    /// (e.g., "!" → "bang", "$" → "dollar") when the current
+    /// symbol level includes them
     ///
     /// Uses pre-compiled regex from Config for efficiency in the hot path.
+    /// Non-ASCII characters without a `[symbols]` entry fall back to
+    /// the built-in Unicode names.
     fn process_symbols_in_text(&self, text: &str) -> String {
-        if !self.config.process_symbols() {
-            return text.to_string();
-        }
-
-        // Use the pre-compiled regex from config
-        if let Some(re) = self.config.symbols_regex() {
-            return re
-                .replace_all(text, |caps: &regex::Captures| {
-                    // Safe unwrap: get(0) always exists in a capture, and we matched at least one char
-                    if let Some(matched) = caps.get(0) {
-                        if let Some(ch) = matched.as_str().chars().next() {
-                            let code = ch as u32;
-                            if let Some(name) = self.config.symbols.get(&code) {
-                                return format!(" {} ", name);
-                            }
-                            return ch.to_string();
-                        }
-                    }
-                    // Fallback: return the original match
-                    caps.get(0)
-                        .map_or(String::new(), |m| m.as_str().to_string())
-                })
-                .to_string();
-        }
+        let level = self.config.symbol_level();
+        let text = match self.config.symbols_regex() {
+            Some(re) => crate::symbols::replace_symbols(
+                text,
+                re,
+                &self.config.symbols,
+                &self.config.symbol_levels,
+                level,
+            ),
+            None => text.to_string(),
+        };
+        crate::unicode_names::replace_names(&text, &self.config.symbols, level)
+    }
 
-        text.to_string()
+    /// Spoken name for a single character, if it has one
+    ///
+    /// The user's `[symbols]` entries take priority over built-in Unicode names.
+    fn char_name(&self, ch: char) -> Option<String> {
+        self.config
+            .symbols
+            .get(&(ch as u32))
+            .cloned()
+            .or_else(|| crate::unicode_names::name(ch).map(str::to_string))
+    }
+
+    /// Cycle to the next symbol level and announce it
+    pub fn cycle_symbol_level(&mut self) -> Result<()> {
+        let level = self.config.symbol_level().next();
+        self.config.set("speech", "symbol_level", level.name());
+        self.save_config()?;
+        info!("Symbol level set to {}", level.name());
+        self.speak(&format!("symbols {}", level.name()))
     }
 
     /// Cancel any pending speech
@@ -363,6 +660,76 @@ impl State {
         self.synth.cancel()
     }
 
+    /// Stop everything in progress when a key is pressed
+    ///
+    /// Stops say-all on the line being read, cancels pending speech
+    /// and clears delayed functions.
+    pub fn interrupt(&mut self) -> Result<()> {
+        self.stop_say_all();
+        self.cancel_speech()?;
+        self.clear_delayed_functions();
+        Ok(())
+    }
+
+    // ========== Speech History ==========
+
+    /// Step back to the previous utterance in history and speak it
+    pub fn history_prev(&mut self) -> Result<()> {
+        if self.history.is_empty() {
+            return self.speak_unrecorded("no history");
+        }
+        if let Some(text) = self.history.step_back().map(str::to_string) {
+            return self.speak_unrecorded(&text);
+        }
+        self.speak_unrecorded("top")?;
+        self.history_current()
+    }
+
+    /// Step forward to the next utterance in history and speak it
+    pub fn history_next(&mut self) -> Result<()> {
+        if self.history.is_empty() {
+            return self.speak_unrecorded("no history");
+        }
+        if let Some(text) = self.history.step_forward().map(str::to_string) {
+            return self.speak_unrecorded(&text);
+        }
+        self.speak_unrecorded("bottom")?;
+        self.history_current()
+    }
+
+    /// Speak the utterance at the current history position
+    fn history_current(&mut self) -> Result<()> {
+        if let Some(text) = self.history.current().map(str::to_string) {
+            self.speak_unrecorded(&text)?;
+        }
+        Ok(())
+    }
+
+    /// Repeat the most recent utterance
+    pub fn repeat_last_utterance(&mut self) -> Result<()> {
+        match self.history.last().map(str::to_string) {
+            Some(text) => self.speak_unrecorded(&text),
+            None => self.speak_unrecorded("no history"),
+        }
+    }
+
+    // ========== Pronunciation Dictionary ==========
+
+    /// Add a dictionary entry saying `replacement` for the whole word `word`
+    ///
+    /// Entries the dictionary file can't hold are refused with a reason.
+    pub fn add_dictionary_entry(&mut self, word: &str, replacement: &str) -> Result<()> {
+        if let Some(reason) = dictionary::unwritable(word, replacement) {
+            info!("Dictionary entry {} not added: {}", word, reason);
+            return self.speak(&format!("not added, {}", reason));
+        }
+        let entry = DictionaryEntry::new(word, replacement, "")?;
+        let path = self.config.dictionary_path();
+        self.dictionary.add(entry, &path)?;
+        info!("Added dictionary entry {} -> {}", word, replacement);
+        self.speak("added")
+    }
+
     // ========== Review Cursor Navigation ==========
     // These methods implement the screen reader's review cursor for
     // navigating and reading screen content independently of the terminal cursor
@@ -374,35 +741,6 @@ impl State {
             .unwrap_or(' ')
     }
 
-    /// Move review cursor to previous character (handles line wrapping)
-    fn move_prevchar(&mut self, screen: &Screen) {
-        let (x, y) = self.review.pos;
-        if x == 0 {
-            if y == 0 {
-                return; // Already at top-left
-            }
-            self.review.pos.1 -= 1;
-            self.review.pos.0 = screen.size.0 - 1;
-        } else {
-            self.review.pos.0 -= 1;
-        }
-    }
-
-    /// Move review cursor to next character (handles line wrapping)
-    fn move_nextchar(&mut self, screen: &Screen) {
-        let (x, y) = self.review.pos;
-        let (cols, rows) = screen.size;
-        if x == cols - 1 {
-            if y == rows - 1 {
-                return; // Already at bottom-right
-            }
-            self.review.pos.1 += 1;
-            self.review.pos.0 = 0;
-        } else {
-            self.review.pos.0 += 1;
-        }
-    }
-
     /// Skip backwards over wide character continuation cells
     /// Screen reader needs to skip these to land on actual characters
     fn skip_to_previous_char(&mut self, screen: &Screen) {
@@ -413,6 +751,7 @@ impl State {
 
     /// Say the line at given y position
     pub fn say_line(&mut self, screen: &Screen, y: u16) -> Result<()> {
+        self.announce_indentation(&screen.get_line(y))?;
         let line = screen.get_line_trimmed(y);
         let text = if line.is_empty() {
             "blank".to_string()
@@ -423,6 +762,63 @@ impl State {
         self.speak(&text)
     }
 
+    /// Announce a line's indentation according to the indentation setting
+    ///
+    /// Tone mode falls back to speech if no audio player is available.
+    fn announce_indentation(&mut self, line: &str) -> Result<()> {
+        let mode = self.config.indentation();
+        if mode == IndentationMode::Off {
+            return Ok(());
+        }
+
+        let always = self.config.indentation_always();
+        if let Some(width) = self.indentation.update(line, always) {
+            if mode == IndentationMode::Tone && !self.quiet {
+                match self.tones.play(
+                    indentation::tone_frequency(width),
+                    Duration::from_millis(60),
+                ) {
+                    Ok(()) => return Ok(()),
+                    Err(e) => debug!("Indentation tone failed: {}", e),
+                }
+            }
+            self.speak(&indentation::describe(width))?;
+        }
+        Ok(())
+    }
+
+    /// Speak the review cursor position, the terminal cursor position
+    /// and the screen size
+    pub fn where_am_i(&mut self, screen: &Screen) -> Result<()> {
+        for phrase in review::describe_position(self.review.pos, screen.cursor, screen.size) {
+            self.speak(&phrase)?;
+        }
+        Ok(())
+    }
+
+    /// Speak the window title and working directory, where known
+    ///
+    /// The directory comes from the shell's OSC 7 reports, or failing
+    /// that from the shell process itself (Linux only).
+    pub fn where_am_i_details(&mut self, screen: &Screen) -> Result<()> {
+        let cwd = screen.cwd.clone().or_else(|| {
+            let pid = self.shell_pid?;
+            let link = std::fs::read_link(format!("/proc/{}/cwd", pid)).ok()?;
+            Some(link.to_string_lossy().into_owned())
+        });
+
+        if screen.title.is_none() && cwd.is_none() {
+            return self.speak("no title or directory");
+        }
+        if let Some(title) = &screen.title {
+            self.speak(&format!("title {}", title))?;
+        }
+        if let Some(cwd) = cwd {
+            self.speak(&format!("directory {}", cwd))?;
+        }
+        Ok(())
+    }
+
     /// Replace duplicate characters with count (e.g., "====" -> "4 equals")
     /// Used to condense repeated symbols for clearer speech
     fn replace_duplicate_characters(&self, line: &str) -> String {
@@ -459,6 +855,91 @@ impl State {
         self.say_line(screen, self.review.pos.1)
     }
 
+    /// Move to the previous or next block of text and speak it
+    ///
+    /// With `by_indent`, moves to the next change in indentation instead
+    /// of the next block separated by blank lines.
+    pub fn move_to_block(
+        &mut self,
+        screen: &Screen,
+        direction: Direction,
+        by_indent: bool,
+    ) -> Result<()> {
+        let lines: Vec<String> = (0..screen.size.1).map(|y| screen.get_line(y)).collect();
+        let from = self.review.pos.1 as usize;
+        let found = if by_indent {
+            blocks::find_indent_change(&lines, from, direction)
+        } else {
+            blocks::find_block(&lines, from, direction)
+        };
+
+        match found {
+            Some(Block { start, lines }) => {
+                self.review.pos = (0, start as u16);
+                self.say_line(screen, start as u16)?;
+                if lines == 1 {
+                    self.speak("1 line")
+                } else {
+                    self.speak(&format!("{} lines", lines))
+                }
+            }
+            None => match direction {
+                Direction::Forward => self.speak("bottom"),
+                Direction::Backward => self.speak("top"),
+            },
+        }
+    }
+
+    /// Find the table around the review cursor line
+    ///
+    /// Announces the table size, or that there is no table.
+    pub fn detect_table(&mut self, screen: &Screen) -> Result<Option<Table>> {
+        let lines: Vec<String> = (0..screen.size.1).map(|y| screen.get_line(y)).collect();
+        match Table::detect(&lines, self.review.pos.1 as usize) {
+            Some(table) => {
+                info!("Table at rows {:?}, columns {:?}", table.rows, table.starts);
+                self.speak(&format!(
+                    "table, {} columns, {} rows",
+                    table.columns(),
+                    table.rows.len()
+                ))?;
+                Ok(Some(table))
+            }
+            None => {
+                self.speak("no table")?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Move the review cursor to a table cell and speak it
+    ///
+    /// Body cells are spoken after their column header.
+    pub fn read_table_cell(
+        &mut self,
+        screen: &Screen,
+        table: &Table,
+        row: usize,
+        column: usize,
+    ) -> Result<()> {
+        self.review.pos = (table.starts[column] as u16, row as u16);
+        let cell = table.cell(&screen.get_line(row as u16), column);
+        let cell = if cell.is_empty() {
+            "blank".to_string()
+        } else {
+            cell
+        };
+        if row == table.rows.start {
+            return self.speak(&cell);
+        }
+        let header = table.cell(&screen.get_line(table.rows.start as u16), column);
+        if header.is_empty() {
+            self.speak(&cell)
+        } else {
+            self.speak(&format!("{}, {}", header, cell))
+        }
+    }
+
     /// Say character at given position
     pub fn say_char(&mut self, screen: &Screen, y: u16, x: u16, phonetic: bool) -> Result<()> {
         let ch = screen.get_char(x, y).unwrap_or(' ');
@@ -469,14 +950,13 @@ impl State {
             }
         }
 
-        // Check if character has a symbol name (always for characters, not just when process_symbols is on)
-        let code = ch as u32;
-        if let Some(name) = self.config.symbols.get(&code).cloned() {
+        // Check if character has a symbol name (always for characters, whatever the symbol level)
+        if let Some(name) = self.char_name(ch) {
             return self.speak(&name);
         }
 
         // Use letter speech command for single characters
-        self.synth.letter(&ch.to_string())
+        self.letter(&ch.to_string())
     }
 
     /// Move to previous character and speak it
@@ -509,39 +989,81 @@ impl State {
         self.say_char(screen, self.review.pos.1, self.review.pos.0, false)
     }
 
-    /// Get word at current position and move cursor to word start
-    /// Returns the word and saves the original cursor position
-    fn get_word_at_cursor(&mut self, screen: &Screen) -> (String, (u16, u16)) {
-        let orig_pos = self.review.pos;
-        let (cols, _) = screen.size;
+    /// Move up or down a row in the same column
+    ///
+    /// Speaks the character there, or its word with `word`. Landing on a
+    /// wide character's continuation cell moves to the character itself,
+    /// but the original column is kept for further vertical moves.
+    pub fn move_vertical(
+        &mut self,
+        screen: &Screen,
+        direction: Direction,
+        word: bool,
+    ) -> Result<()> {
+        let column = match self.vertical_column {
+            Some((column, landed)) if landed == self.review.pos => column,
+            _ => self.review.pos.0,
+        };
 
-        // Move to beginning of word
-        while self.review.pos.0 > 0
-            && self.get_char(screen) != ' '
-            && screen.get_char(self.review.pos.0 - 1, self.review.pos.1) != Some(' ')
-        {
-            self.move_prevchar(screen);
+        let y = self.review.pos.1;
+        match direction {
+            Direction::Backward if y == 0 => self.speak("top")?,
+            Direction::Backward => self.review.pos.1 -= 1,
+            Direction::Forward if y >= screen.size.1 - 1 => self.speak("bottom")?,
+            Direction::Forward => self.review.pos.1 += 1,
         }
+        self.review.pos.0 = column.min(screen.size.0 - 1);
+        self.skip_to_previous_char(screen);
+        self.vertical_column = Some((column, self.review.pos));
 
-        // At start of line with space? That's just "space"
-        if self.review.pos.0 == 0 && self.get_char(screen) == ' ' {
-            return ("".to_string(), orig_pos);
+        if word {
+            self.say_word(screen, false)
+        } else {
+            self.say_char(screen, self.review.pos.1, self.review.pos.0, false)
         }
+    }
 
-        // Collect the word
-        let mut word = String::new();
-        word.push(self.get_char(screen));
-
-        while self.review.pos.0 < cols - 1 {
-            self.move_nextchar(screen);
-            let ch = self.get_char(screen);
-            if ch == ' ' {
-                break;
+    /// Get word at current position and move cursor to word start
+    /// Returns the word and saves the original cursor position
+    ///
+    /// Words follow the configured separators and sub-word mode. On
+    /// whitespace the word is empty; on another separator it is that
+    /// character.
+    fn get_word_at_cursor(&mut self, screen: &Screen) -> (String, (u16, u16)) {
+        let orig_pos = self.review.pos;
+        let line = Self::line_cells(screen, orig_pos.1);
+        let rules = self.config.word_rules();
+
+        match rules.word_at(&line, orig_pos.0 as usize) {
+            Some(span) => {
+                self.review.pos.0 = span.start as u16;
+                let word = line[span].iter().filter(|&&ch| ch != '\0').collect();
+                (word, orig_pos)
+            }
+            None => {
+                let ch = self.get_char(screen);
+                let word = if ch.is_whitespace() {
+                    String::new()
+                } else {
+                    ch.to_string()
+                };
+                (word, orig_pos)
             }
-            word.push(ch);
         }
+    }
 
-        (word, orig_pos)
+    /// Characters of a screen row by column, '\0' for wide continuations
+    fn line_cells(screen: &Screen, y: u16) -> Vec<char> {
+        (0..screen.size.0)
+            .map(|x| screen.get_char(x, y).unwrap_or(' '))
+            .collect()
+    }
+
+    /// Get the word under the review cursor without moving it
+    pub fn word_at_review_cursor(&mut self, screen: &Screen) -> String {
+        let (word, orig_pos) = self.get_word_at_cursor(screen);
+        self.review.pos = orig_pos;
+        word
     }
 
     /// Say word at current position (with optional spelling)
@@ -551,9 +1073,12 @@ impl State {
         if word.is_empty() {
             self.speak("space")?;
         } else if spell {
-            // Spell the word letter by letter
+            // Spell the word letter by letter, naming symbols in full
             for ch in word.chars() {
-                self.synth.letter(&ch.to_string())?;
+                match self.char_name(ch) {
+                    Some(name) => self.letter(&name)?,
+                    None => self.letter(&ch.to_string())?,
+                }
             }
         } else {
             self.speak(&word)?;
@@ -566,54 +1091,27 @@ impl State {
 
     /// Move to previous word and speak it
     pub fn prev_word(&mut self, screen: &Screen) -> Result<()> {
-        if self.review.pos.0 == 0 {
-            self.speak("left")?;
-            return self.say_word(screen, false);
-        }
-
-        // Move over any existing word we're in
-        while self.review.pos.0 > 0 && self.get_char(screen) != ' ' {
-            self.move_prevchar(screen);
-        }
+        let line = Self::line_cells(screen, self.review.pos.1);
+        let rules = self.config.word_rules();
+        let x = self.review.pos.0 as usize;
 
-        // Skip whitespace
-        while self.review.pos.0 > 0 && self.get_char(screen) == ' ' {
-            self.move_prevchar(screen);
+        match rules.prev_word_start(&line, x) {
+            Some(start) => self.review.pos.0 = start as u16,
+            None => self.speak("left")?,
         }
-
-        // Move to beginning of the word we're now on
-        while self.review.pos.0 > 0
-            && self.get_char(screen) != ' '
-            && screen.get_char(self.review.pos.0 - 1, self.review.pos.1) != Some(' ')
-        {
-            self.move_prevchar(screen);
-        }
-
         self.say_word(screen, false)
     }
 
     /// Move to next word and speak it
     pub fn next_word(&mut self, screen: &Screen) -> Result<()> {
-        let (cols, _) = screen.size;
-        let orig_pos = self.review.pos;
+        let line = Self::line_cells(screen, self.review.pos.1);
+        let rules = self.config.word_rules();
+        let x = self.review.pos.0 as usize;
 
-        // Move over current word
-        while self.review.pos.0 < cols - 1 && self.get_char(screen) != ' ' {
-            self.move_nextchar(screen);
+        match rules.next_word_start(&line, x) {
+            Some(start) => self.review.pos.0 = start as u16,
+            None => self.speak("right")?,
         }
-
-        // Skip whitespace
-        while self.review.pos.0 < cols - 1 && self.get_char(screen) == ' ' {
-            self.move_nextchar(screen);
-        }
-
-        // Hit right edge on whitespace?
-        if self.review.pos.0 == cols - 1 && self.get_char(screen) == ' ' {
-            self.speak("right")?;
-            self.review.pos = orig_pos;
-            return self.say_word(screen, false);
-        }
-
         self.say_word(screen, false)
     }
 
@@ -667,6 +1165,448 @@ impl State {
             .is_some_and(|pm| pm.has_plugin(key))
     }
 
+    // ========== Screen Regions ==========
+
+    /// Check if a key reads a region
+    pub fn has_region_key(&self, key: char) -> bool {
+        self.regions.for_key(key).is_some()
+    }
+
+    /// Read the region bound to a key
+    pub fn read_region(&mut self, key: char, screen: &Screen) -> Result<()> {
+        let lines: Vec<String> = (0..screen.size.1).map(|y| screen.get_line(y)).collect();
+        let content = match self.regions.for_key(key) {
+            Some(region) => region.content(&lines, screen.size),
+            None => return Ok(()),
+        };
+        if content.is_empty() {
+            self.speak("blank")
+        } else {
+            self.speak(&content.join("\n"))
+        }
+    }
+
+    /// Announce watched regions whose content has changed
+    ///
+    /// Spoken even in quiet mode.
+    pub fn check_regions(&mut self, screen: &Screen) -> Result<()> {
+        let lines: Vec<String> = (0..screen.size.1).map(|y| screen.get_line(y)).collect();
+        for content in self.regions.changed(&lines, screen.size, Instant::now()) {
+            if !content.is_empty() {
+                self.speak_despite_quiet(&content.join("\n"))?;
+            }
+        }
+        Ok(())
+    }
+
+    // ========== Highlight Tracking ==========
+
+    /// Note new output, so highlights are checked once it settles
+    pub fn output_received(&mut self) {
+        self.highlight_due = Some(Instant::now() + HIGHLIGHT_SETTLE);
+    }
+
+    /// Speak a highlight that has moved or changed since the last check
+    ///
+    /// Only done in the alternate screen, where menu-driven programs run.
+    /// The review cursor moves onto the highlighted text.
+    pub fn check_highlight(&mut self, screen: &Screen) -> Result<()> {
+        match self.highlight_due {
+            Some(due) if Instant::now() >= due => self.highlight_due = None,
+            _ => return Ok(()),
+        }
+        if !self.config.highlight_tracking() || !screen.in_alternate_screen() {
+            self.highlight.reset();
+            return Ok(());
+        }
+
+        if let Some(found) = self.highlight.update(highlight::find_highlights(screen)) {
+            debug!("Highlight at {:?}: '{}'", (found.x, found.y), found.text);
+            // Status bars (a clock, "[+]", "(END)") aren't a selection,
+            // so they don't take over
+            if !found.status_bar {
+                self.review.pos = (found.x, found.y);
+                // The redraw was probably being read as output; the selection matters more
+                self.cancel_speech()?;
+            }
+            self.speak(&found.text)?;
+        }
+        Ok(())
+    }
+
+    // ========== Command Output ==========
+
+    /// Lines of the last command's output
+    ///
+    /// Uses shell integration marks if the shell sends them. Otherwise
+    /// looks back from the cursor line for the previous prompt, matched
+    /// with the `prompt` setting or, if that is left matching everything,
+    /// by the text before input starts on the current prompt.
+    pub fn last_output(&self, screen: &Screen) -> Option<Vec<String>> {
+        let lines = screen.all_lines();
+        let range = match screen.last_output_range() {
+            Some(range) => output::trim_blank_end(&lines, range),
+            None => {
+                let current = screen.scrollback_len() + screen.cursor.1 as usize;
+                let pattern = self.config.prompt_pattern();
+                match regex::Regex::new(&pattern) {
+                    Ok(prompt) if pattern != ".*" => {
+                        output::find_by_prompt(&lines, current, |line| prompt.is_match(line))
+                    }
+                    _ => {
+                        // Text already typed for the next command isn't part of the prompt
+                        let input = screen.input_column().unwrap_or(screen.cursor.0);
+                        let prefix = output::prompt_prefix(lines.get(current)?, input as usize)?;
+                        output::find_by_prompt(&lines, current, |line| line.starts_with(&prefix))
+                    }
+                }
+            }
+        }?;
+        Some(lines[range].to_vec())
+    }
+
+    /// Read the last command's output
+    pub fn read_last_output(&mut self, screen: &Screen) -> Result<()> {
+        match self.last_output(screen) {
+            Some(lines) => self.speak(&lines.join("\n")),
+            None => self.speak("no output"),
+        }
+    }
+
+    // ========== Say All ==========
+
+    /// Start reading continuously from the review cursor line
+    ///
+    /// With `from_top`, reading starts at the oldest scrollback line.
+    pub fn start_say_all(&mut self, screen: &Screen, from_top: bool) -> Result<()> {
+        if self.quiet {
+            return Ok(());
+        }
+        let from = if from_top {
+            0
+        } else {
+            screen.scrollback_len() + self.review.pos.1 as usize
+        };
+        info!("Say all from line {}", from);
+        self.say_all = Some(SayAll::new(from));
+        self.continue_say_all(screen)
+    }
+
+    /// Stop reading, leaving the review cursor on the current line
+    pub fn stop_say_all(&mut self) {
+        if self.say_all.take().is_some() {
+            info!("Say all stopped");
+        }
+    }
+
+    /// Read the next line once the last one has been spoken
+    ///
+    /// Called from the event loop. Screen lines move the review cursor
+    /// as they are read.
+    pub fn continue_say_all(&mut self, screen: &Screen) -> Result<()> {
+        let now = Instant::now();
+        let speaking = match &self.say_all {
+            Some(_) => self.synth.is_speaking(),
+            None => return Ok(()),
+        };
+        let lines = screen.all_lines();
+        let line = match self.say_all.as_mut() {
+            Some(say_all) if say_all.ready(speaking, now) => say_all.next_line(&lines),
+            _ => return Ok(()),
+        };
+        let line = match line {
+            Some(line) => line,
+            None => {
+                self.stop_say_all();
+                return Ok(());
+            }
+        };
+
+        let offset = screen.scrollback_len();
+        if line >= offset {
+            let y = (line - offset) as u16;
+            self.review.pos = (0, y);
+        }
+        let text = &lines[line];
+        self.speak(text)?;
+
+        let rate = self.config.rate().unwrap_or(50);
+        if let Some(say_all) = self.say_all.as_mut() {
+            say_all.started(text, rate, now);
+        }
+        Ok(())
+    }
+
+    // ========== Bookmarks ==========
+
+    /// Set a bookmark at the review cursor
+    pub fn set_bookmark(&mut self, name: char) -> Result<()> {
+        self.bookmarks.set(name, self.review.pos);
+        self.speak(&format!("bookmark {} set", name))
+    }
+
+    /// Look up a bookmark, announcing it if missing or stale
+    fn bookmark_position(&mut self, name: char) -> Result<Option<(u16, u16)>> {
+        match self.bookmarks.get(name) {
+            Some(Bookmark::At(x, y)) => Ok(Some((x, y))),
+            Some(Bookmark::Stale) => {
+                self.speak(&format!("bookmark {} stale", name))?;
+                Ok(None)
+            }
+            None => {
+                self.speak(&format!("no bookmark {}", name))?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Move the review cursor to a bookmark and read its line
+    pub fn jump_to_bookmark(&mut self, name: char, screen: &Screen) -> Result<()> {
+        if let Some(pos) = self.bookmark_position(name)? {
+            self.review.pos = pos;
+            self.say_line(screen, pos.1)?;
+        }
+        Ok(())
+    }
+
+    /// Read a bookmark's line without moving the review cursor
+    pub fn read_bookmark(&mut self, name: char, screen: &Screen) -> Result<()> {
+        if let Some((_, y)) = self.bookmark_position(name)? {
+            self.say_line(screen, y)?;
+        }
+        Ok(())
+    }
+
+    // ========== Search ==========
+
+    /// Start a new search and move to its first match
+    pub fn start_search(&mut self, query: &str, is_regex: bool, screen: &Screen) -> Result<()> {
+        match Search::new(query, is_regex, self.config.search_ignore_case()) {
+            Ok(search) => {
+                info!("Searching for '{}'", search.query);
+                self.search = Some(search);
+                self.last_match = None;
+                self.find(screen, Direction::Forward)
+            }
+            Err(e) => {
+                info!("Search not started: {}", e);
+                self.speak(if query.is_empty() {
+                    "nothing to find"
+                } else {
+                    "invalid search"
+                })
+            }
+        }
+    }
+
+    /// Move to the next or previous match of the current search
+    ///
+    /// Matches on screen move the review cursor there and read the line.
+    /// Matches in the scrollback are read out, since the review cursor
+    /// can't leave the screen; the next search continues from them.
+    pub fn find(&mut self, screen: &Screen, direction: Direction) -> Result<()> {
+        let lines = screen.all_lines();
+        let offset = screen.scrollback_len();
+        let review = Match {
+            line: offset + self.review.pos.1 as usize,
+            column: self.review.pos.0 as usize,
+        };
+        let first = screen.first_line_number();
+        let from = match self.last_match {
+            Some((number, column)) if number >= first && number - first < offset as u64 => Match {
+                line: (number - first) as usize,
+                column,
+            },
+            _ => review,
+        };
+
+        let found = match &self.search {
+            Some(search) => search.find(&lines, from, direction),
+            None => return self.speak("no search"),
+        };
+        let found = match found {
+            Some(found) => found,
+            None => return self.speak("not found"),
+        };
+
+        self.last_match = Some((first + found.at.line as u64, found.at.column));
+        if found.wrapped {
+            self.speak("wrapped")?;
+        }
+        if found.at.line >= offset {
+            let y = (found.at.line - offset) as u16;
+            self.review.pos = (found.at.column as u16, y);
+            self.say_line(screen, y)
+        } else {
+            self.speak("scrollback")?;
+            self.speak(&lines[found.at.line])
+        }
+    }
+
+    // ========== Braille Display ==========
+
+    /// File descriptor of the braille display connection, if connected
+    pub fn braille_fd(&self) -> Option<std::os::unix::io::RawFd> {
+        self.braille.as_ref().map(BrailleDisplay::as_raw_fd)
+    }
+
+    /// Read commands from braille display keys
+    ///
+    /// Drops the display if the connection fails so the rest of the
+    /// screen reader keeps working.
+    pub fn read_braille_commands(&mut self) -> Vec<braille::BrailleCommand> {
+        match self.braille.as_mut().map(BrailleDisplay::read_commands) {
+            Some(Ok(commands)) => commands,
+            Some(Err(e)) => {
+                info!("Braille display disconnected: {}", e);
+                self.braille = None;
+                Vec::new()
+            }
+            None => Vec::new(),
+        }
+    }
+
+    /// Act on a key pressed on the braille display
+    ///
+    /// Like any key, a display key first interrupts speech and say-all,
+    /// so reading doesn't carry the review cursor away from where the
+    /// key put it.
+    pub fn braille_command(
+        &mut self,
+        command: braille::BrailleCommand,
+        emulator: &mut Emulator,
+        default_handler: &mut DefaultKeyHandler,
+    ) -> Result<()> {
+        self.interrupt()?;
+
+        let screen = emulator.screen();
+        match command {
+            braille::BrailleCommand::Action(action) => {
+                default_handler.execute_action(&action, self, emulator)?;
+            }
+            braille::BrailleCommand::PanLeft => self.braille_pan_left(screen)?,
+            braille::BrailleCommand::PanRight => self.braille_pan_right(screen)?,
+            braille::BrailleCommand::Route(cell) => self.braille_route(screen, cell)?,
+        }
+        Ok(())
+    }
+
+    /// Show the line under the review cursor on the braille display
+    pub fn refresh_braille(&mut self, screen: &Screen) {
+        let pos = self.review.pos;
+        if let Some(display) = self.braille.as_mut() {
+            if let Err(e) = display.show(screen, pos) {
+                info!("Braille display disconnected: {}", e);
+                self.braille = None;
+            }
+        }
+    }
+
+    /// Pan the braille display left, moving the review cursor with it
+    pub fn braille_pan_left(&mut self, screen: &Screen) -> Result<()> {
+        let width = match self.braille.as_ref() {
+            Some(display) => display.width(),
+            None => return Ok(()),
+        };
+        match braille::pan_left(screen, self.review.pos, width) {
+            Some(pos) => self.review.pos = pos,
+            None => self.speak("top")?,
+        }
+        Ok(())
+    }
+
+    /// Pan the braille display right, moving the review cursor with it
+    pub fn braille_pan_right(&mut self, screen: &Screen) -> Result<()> {
+        let width = match self.braille.as_ref() {
+            Some(display) => display.width(),
+            None => return Ok(()),
+        };
+        match braille::pan_right(screen, self.review.pos, width) {
+            Some(pos) => self.review.pos = pos,
+            None => self.speak("bottom")?,
+        }
+        Ok(())
+    }
+
+    /// Move the review cursor to the cell under a routing key and speak it
+    pub fn braille_route(&mut self, screen: &Screen, cell: u16) -> Result<()> {
+        let width = match self.braille.as_ref() {
+            Some(display) => display.width(),
+            None => return Ok(()),
+        };
+        if let Some(x) = braille::route_column(self.review.pos, width, cell, screen.size.0) {
+            self.review.pos.0 = x;
+            self.skip_to_previous_char(screen);
+        }
+        self.current_char(screen, false)
+    }
+
+    // ========== Cursor Routing ==========
+
+    /// Move the terminal cursor to the review cursor
+    ///
+    /// Sends arrow keys to the program. With application cursor keys on,
+    /// as in editors, rows are moved with up and down. Otherwise the
+    /// cursor is moved along the line, which follows a wrapped shell
+    /// command line without recalling history. The final position is
+    /// checked once the program has had time to respond.
+    pub fn route_cursor(&mut self, screen: &Screen) -> Result<()> {
+        let from = screen.cursor;
+        let to = self.review.pos;
+        if from == to {
+            return self.say_char(screen, to.1, to.0, false);
+        }
+
+        // Characters between two positions, reading left to right and
+        // down, not counting wide character continuation cells
+        let chars_between = |a: (u16, u16), b: (u16, u16)| -> i32 {
+            let cols = screen.size.0 as usize;
+            let start = a.1 as usize * cols + a.0 as usize;
+            let end = b.1 as usize * cols + b.0 as usize;
+            (start..end)
+                .filter(|&i| {
+                    let (x, y) = ((i % cols) as u16, (i / cols) as u16);
+                    screen.get_char(x, y) != Some('\0')
+                })
+                .count() as i32
+        };
+        let app_cursor = screen.app_cursor_keys;
+        let (rows, chars) = if app_cursor {
+            let row_start = (0, to.1);
+            let column = chars_between(row_start, to) - chars_between(row_start, (from.0, to.1));
+            (to.1 as i32 - from.1 as i32, column)
+        } else if (to.1, to.0) > (from.1, from.0) {
+            (0, chars_between(from, to))
+        } else {
+            (0, -chars_between(to, from))
+        };
+
+        info!("Routing cursor from {:?} to {:?}", from, to);
+        self.pending_input
+            .extend(routing::route_keys(rows, chars, app_cursor));
+
+        let delay = Duration::from_secs_f32(self.config.cursor_delay()).max(ROUTE_CHECK_DELAY);
+        self.schedule(
+            delay,
+            move |state, screen| {
+                let (x, y) = screen.cursor;
+                if (x, y) == to {
+                    state.say_char(screen, y, x, false)
+                } else {
+                    debug!("Cursor routed to {:?} instead of {:?}", (x, y), to);
+                    state.speak(&format!("cursor at row {}, column {}", y + 1, x + 1))
+                }
+            },
+            true,
+        );
+        Ok(())
+    }
+
+    /// Take keys queued for the shell
+    pub fn take_pending_input(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.pending_input)
+    }
+
     // ========== Cursor Tracking / Delayed Functions ==========
 
     /// Schedule a function to run after a delay
@@ -726,14 +1666,23 @@ impl State {
     /// Get time until next scheduled function
     ///
     /// Returns None if no functions are scheduled, otherwise duration until next function
+    /// Also covers the end of an output flood, of the speech coalescing
+    /// window, of watched region changes and of highlight settling, so
+    /// none is delayed, and polls say-all while it runs
     /// Used to set timeout for select/poll
     pub fn time_until_next_scheduled(&self) -> Option<Duration> {
-        if self.delayed_functions.is_empty() {
-            return None;
-        }
-
         let now = Instant::now();
-        let next = self.delayed_functions.iter().map(|(when, _)| *when).min()?;
+        let next = self
+            .delayed_functions
+            .iter()
+            .map(|(when, _)| *when)
+            .chain(self.flood.settle_deadline())
+            .chain(self.speech_buffer.deadline())
+            .chain(self.regions.deadline())
+            .chain(self.highlight_due)
+            .chain(self.selection_copy.map(|(due, _)| due))
+            .chain(self.say_all.as_ref().map(|_| now + say_all::POLL_INTERVAL))
+            .min()?;
 
         Some(next.saturating_duration_since(now))
     }
@@ -759,6 +1708,8 @@ impl State {
             return;
         }
 
+        self.bookmarks.adjust_for_scroll(scroll_offset, rows);
+
         let (x, y) = self.review.pos;
         let new_y = if scroll_offset > 0 {
             // Content scrolled up - review cursor should move up to follow
@@ -775,7 +1726,95 @@ impl State {
 
 #[cfg(test)]
 mod tests {
-    use crate::terminal::Screen;
+    use super::testing::test_state;
+    use crate::braille::BrailleCommand;
+    use crate::input::{create_default_keymap, DefaultKeyHandler, KeyAction};
+    use crate::review::search::{Direction, Search};
+    use crate::review::selection::linear_text;
+    use crate::terminal::{Emulator, Screen};
+    use std::time::Instant;
+
+    #[test]
+    fn test_braille_key_stops_say_all() {
+        let (mut state, _) = test_state(10, 4);
+        let mut emulator = Emulator::new(10, 4);
+        emulator.process(b"one\r\ntwo\r\nthree").unwrap();
+        let mut handler = DefaultKeyHandler::new(create_default_keymap());
+
+        state.start_say_all(emulator.screen(), true).unwrap();
+        assert!(state.say_all.is_some());
+
+        let command = BrailleCommand::Action(KeyAction::BottomOfScreen);
+        state
+            .braille_command(command, &mut emulator, &mut handler)
+            .unwrap();
+        assert!(state.say_all.is_none());
+
+        // Reading doesn't carry on and move the review cursor
+        let pos = state.review.pos;
+        state.continue_say_all(emulator.screen()).unwrap();
+        assert_eq!(state.review.pos, pos);
+    }
+
+    #[test]
+    fn test_selection_extent_spoken_with_movement() {
+        let (mut state, spoken) = test_state(20, 2);
+        let mut emulator = Emulator::new(20, 2);
+        emulator.process(b"hello world").unwrap();
+        let mut handler = DefaultKeyHandler::new(create_default_keymap());
+
+        state.review.pos = (0, 0);
+        state.start_selection();
+        handler
+            .execute_action(&KeyAction::NextWord, &mut state, &mut emulator)
+            .unwrap();
+
+        assert_eq!(*spoken.lock().unwrap(), vec!["world, 7 characters"]);
+        assert_eq!(state.history.last(), Some("world"));
+        assert_eq!(state.history.len(), 1);
+    }
+
+    #[test]
+    fn test_review_follows_full_width_selection_bar() {
+        let (mut state, spoken) = test_state(20, 5);
+        state.config.set("speech", "highlight_tracking", "true");
+        let mut emulator = Emulator::new(20, 5);
+        emulator
+            .process(b"\x1b[?1049h\x1b[2;1H\x1b[7m  1 root  init      \x1b[m")
+            .unwrap();
+        state.highlight_due = Some(Instant::now());
+        state.check_highlight(emulator.screen()).unwrap();
+
+        emulator
+            .process(b"\x1b[2;1H  1 root  init      \x1b[3;1H\x1b[7m 42 me    bash      \x1b[m")
+            .unwrap();
+        state.highlight_due = Some(Instant::now());
+        state.check_highlight(emulator.screen()).unwrap();
+
+        assert_eq!(state.review.pos, (1, 2));
+        assert_eq!(spoken.lock().unwrap().last().unwrap(), "42 me    bash");
+    }
+
+    #[test]
+    fn test_find_next_after_scrollback_drops_lines() {
+        let (mut state, spoken) = test_state(10, 2);
+        let mut emulator = Emulator::new(10, 2);
+        emulator.screen_mut().set_scrollback_limit(4);
+        emulator
+            .process(b"a\r\nmatch 1\r\nb\r\nmatch 2\r\nc\r\nd")
+            .unwrap();
+
+        // Scrollback: a, match 1, b, match 2; screen: c, d
+        state.search = Some(Search::new("match", false, true).unwrap());
+        state.review.pos = (0, 0);
+        state.find(emulator.screen(), Direction::Backward).unwrap();
+        assert_eq!(spoken.lock().unwrap().last().unwrap(), "match 2");
+
+        // The oldest line is dropped, shifting every index down by one
+        emulator.process(b"\r\ne").unwrap();
+        state.find(emulator.screen(), Direction::Backward).unwrap();
+        assert_eq!(spoken.lock().unwrap().last().unwrap(), "match 1");
+    }
 
     /// Test helper to create a screen with test content
     fn create_test_screen() -> Screen {
@@ -795,40 +1834,9 @@ mod tests {
         screen
     }
 
-    /// Test helper to extract text from screen using the same logic as copy_text_range
+    /// Test helper to extract text from screen as a linear selection
     fn extract_text(screen: &Screen, start_x: u16, start_y: u16, end_x: u16, end_y: u16) -> String {
-        let mut start_x = start_x;
-        let mut start_y = start_y;
-        let mut end_x = end_x;
-        let mut end_y = end_y;
-
-        // Same normalization logic as copy_text_range
-        if start_y > end_y || (start_y == end_y && start_x > end_x) {
-            std::mem::swap(&mut start_x, &mut end_x);
-            std::mem::swap(&mut start_y, &mut end_y);
-        }
-
-        let mut text = String::new();
-        let (cols, _) = screen.size;
-
-        for y in start_y..=end_y {
-            let line_start = if y == start_y { start_x } else { 0 };
-            let line_end = if y == end_y { end_x } else { cols - 1 };
-
-            for x in line_start..=line_end {
-                if let Some(ch) = screen.get_char(x, y) {
-                    if ch != '\0' {
-                        text.push(ch);
-                    }
-                }
-            }
-
-            if y < end_y {
-                text.push('\n');
-            }
-        }
-
-        text
+        linear_text(screen, (start_x, start_y), (end_x, end_y))
     }
 
     #[test]
@@ -0,0 +1,61 @@
+//! State for unit tests
+//!
+//! Builds a State without reading the user's config or starting a
+//! speech backend.
+
+use super::config::Config;
+use super::State;
+use crate::speech::{Dictionary, SpeechCommand, Synth};
+use crate::Result;
+use std::sync::{Arc, Mutex};
+
+/// Synthesizer that records what it is asked to say
+#[derive(Clone, Default)]
+pub struct RecordingSynth {
+    pub spoken: Arc<Mutex<Vec<String>>>,
+}
+
+impl Synth for RecordingSynth {
+    fn send(&mut self, _cmd: SpeechCommand) -> Result<()> {
+        Ok(())
+    }
+
+    fn set_rate(&mut self, _rate: u8) -> Result<()> {
+        Ok(())
+    }
+
+    fn set_volume(&mut self, _volume: u8) -> Result<()> {
+        Ok(())
+    }
+
+    fn set_voice_idx(&mut self, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+
+    fn speak(&mut self, text: &str) -> Result<()> {
+        self.spoken.lock().unwrap().push(text.to_string());
+        Ok(())
+    }
+
+    fn letter(&mut self, text: &str) -> Result<()> {
+        self.speak(text)
+    }
+
+    fn cancel(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn is_speaking(&mut self) -> Option<bool> {
+        Some(false)
+    }
+}
+
+/// State with the default configuration, an empty dictionary and a
+/// synthesizer recording what is spoken
+pub fn test_state(cols: u16, rows: u16) -> (State, Arc<Mutex<Vec<String>>>) {
+    let synth = RecordingSynth::default();
+    let spoken = synth.spoken.clone();
+    let mut state = State::with_synth(Config::defaults(), Box::new(synth), cols, rows).unwrap();
+    state.dictionary = Dictionary::new();
+    (state, spoken)
+}
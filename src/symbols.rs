@@ -2,14 +2,151 @@
 //!
 //! This module provides utilities for processing special characters in speech output:
 //! - `condense_repeated_chars`: Converts repeated symbols like "====" into "4 equals"
-//!
-//! Note: Symbol-to-name replacement (e.g., "!" → "bang") is handled by
-//! `State::process_symbols_in_text` using a cached compiled regex for efficiency.
+//! - `replace_symbols`: Converts symbols to names (e.g., "!" → "bang") according
+//!   to the current `SymbolLevel`, using the regex Config compiles once at load
 //!
 //! Note: Phonetic alphabet is defined in `state/phonetics.rs` as the `PHONETICS` map.
 
+use regex::Regex;
 use std::collections::HashMap;
 
+/// How much punctuation is spoken when reading text
+///
+/// Every symbol has a level; it is spoken when the current level is at
+/// or above it. Symbols at `Character` level are only spoken when reading
+/// single characters (or when the current level is `Character`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SymbolLevel {
+    None,
+    Some,
+    Most,
+    All,
+    Character,
+}
+
+impl SymbolLevel {
+    /// All levels in cycling order
+    pub const ALL: [SymbolLevel; 5] = [
+        SymbolLevel::None,
+        SymbolLevel::Some,
+        SymbolLevel::Most,
+        SymbolLevel::All,
+        SymbolLevel::Character,
+    ];
+
+    /// Parse a level name as written in the config file
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|level| level.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Level name as written in the config file and spoken to the user
+    pub fn name(self) -> &'static str {
+        match self {
+            SymbolLevel::None => "none",
+            SymbolLevel::Some => "some",
+            SymbolLevel::Most => "most",
+            SymbolLevel::All => "all",
+            SymbolLevel::Character => "character",
+        }
+    }
+
+    /// The next level, wrapping from `Character` back to `None`
+    pub fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|&l| l == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+}
+
+/// Built-in symbol names and levels (character code, name, level)
+///
+/// Used to write the default `[symbols]` section, and to give untagged
+/// entries from older config files a sensible level.
+pub const DEFAULT_SYMBOLS: &[(u32, &str, SymbolLevel)] = &[
+    (32, "space", SymbolLevel::Character),
+    (33, "bang", SymbolLevel::All),
+    (34, "quote", SymbolLevel::Most),
+    (35, "number", SymbolLevel::Most),
+    (36, "dollar", SymbolLevel::All),
+    (37, "percent", SymbolLevel::Some),
+    (38, "and", SymbolLevel::Some),
+    (39, "tick", SymbolLevel::All),
+    (40, "left paren", SymbolLevel::Most),
+    (41, "right paren", SymbolLevel::Most),
+    (42, "star", SymbolLevel::Some),
+    (43, "plus", SymbolLevel::Some),
+    (44, "comma", SymbolLevel::All),
+    (45, "dash", SymbolLevel::Most),
+    (46, "dot", SymbolLevel::All),
+    (47, "slash", SymbolLevel::Some),
+    (58, "colon", SymbolLevel::Most),
+    (59, "semi", SymbolLevel::Most),
+    (60, "less", SymbolLevel::Most),
+    (61, "equals", SymbolLevel::Some),
+    (62, "greater", SymbolLevel::Most),
+    (63, "question", SymbolLevel::All),
+    (64, "at", SymbolLevel::Some),
+    (91, "left bracket", SymbolLevel::Most),
+    (92, "backslash", SymbolLevel::Most),
+    (93, "right bracket", SymbolLevel::Most),
+    (94, "caret", SymbolLevel::Most),
+    (95, "line", SymbolLevel::Most),
+    (96, "grav", SymbolLevel::Most),
+    (123, "left brace", SymbolLevel::Most),
+    (124, "bar", SymbolLevel::Most),
+    (125, "right brace", SymbolLevel::Most),
+    (126, "tilda", SymbolLevel::Most),
+];
+
+/// Default level for a symbol with no level in the config
+pub fn default_symbol_level(code: u32) -> SymbolLevel {
+    DEFAULT_SYMBOLS
+        .iter()
+        .find(|(c, _, _)| *c == code)
+        .map_or(SymbolLevel::All, |(_, _, level)| *level)
+}
+
+/// Split a `[symbols]` value like "bang, all" into name and level
+///
+/// Values without a recognised level after the last comma are taken
+/// whole as the name, with the default level for that character.
+pub fn parse_symbol_entry(code: u32, value: &str) -> (String, SymbolLevel) {
+    if let Some((name, level)) = value.rsplit_once(',') {
+        if let Some(level) = SymbolLevel::from_name(level) {
+            return (name.trim().to_string(), level);
+        }
+    }
+    (value.trim().to_string(), default_symbol_level(code))
+}
+
+/// Replace symbols with their names if their level is within `level`
+///
+/// `regex` matches any single symbol character from `symbols`.
+pub fn replace_symbols(
+    text: &str,
+    regex: &Regex,
+    symbols: &HashMap<u32, String>,
+    levels: &HashMap<u32, SymbolLevel>,
+    level: SymbolLevel,
+) -> String {
+    regex
+        .replace_all(text, |caps: &regex::Captures| {
+            let matched = &caps[0];
+            let code = matched.chars().next().map_or(0, |ch| ch as u32);
+            let symbol_level = levels
+                .get(&code)
+                .copied()
+                .unwrap_or_else(|| default_symbol_level(code));
+
+            match symbols.get(&code) {
+                Some(name) if symbol_level <= level => format!(" {} ", name),
+                _ => matched.to_string(),
+            }
+        })
+        .to_string()
+}
+
 /// Replace repeated characters with count + character
 /// e.g., "====" becomes "4 equals"
 pub fn condense_repeated_chars(
@@ -86,4 +223,62 @@ mod tests {
         let result = condense_repeated_chars("hello===world", "=", &symbols);
         assert_eq!(result, "hello3 =world");
     }
+
+    #[test]
+    fn test_symbol_level_names_and_cycle() {
+        assert_eq!(SymbolLevel::from_name("Most"), Some(SymbolLevel::Most));
+        assert_eq!(SymbolLevel::from_name("loud"), None);
+        assert_eq!(SymbolLevel::None.next(), SymbolLevel::Some);
+        assert_eq!(SymbolLevel::Character.next(), SymbolLevel::None);
+        for level in SymbolLevel::ALL {
+            assert_eq!(SymbolLevel::from_name(level.name()), Some(level));
+        }
+    }
+
+    #[test]
+    fn test_parse_symbol_entry() {
+        assert_eq!(
+            parse_symbol_entry(33, "bang, some"),
+            ("bang".to_string(), SymbolLevel::Some)
+        );
+        // Untagged entries from older configs get the built-in level
+        assert_eq!(
+            parse_symbol_entry(33, "bang"),
+            ("bang".to_string(), SymbolLevel::All)
+        );
+        // A comma not followed by a level is part of the name
+        assert_eq!(
+            parse_symbol_entry(1000, "one, two"),
+            ("one, two".to_string(), SymbolLevel::All)
+        );
+    }
+
+    #[test]
+    fn test_replace_symbols_by_level() {
+        let mut symbols = HashMap::new();
+        let mut levels = HashMap::new();
+        for (code, name, level) in DEFAULT_SYMBOLS {
+            symbols.insert(*code, name.to_string());
+            levels.insert(*code, *level);
+        }
+        let regex = Regex::new(r"[.,#%]").unwrap();
+        let text = "Done, 50% of #3.";
+
+        assert_eq!(
+            replace_symbols(text, &regex, &symbols, &levels, SymbolLevel::None),
+            text
+        );
+        assert_eq!(
+            replace_symbols(text, &regex, &symbols, &levels, SymbolLevel::Some),
+            "Done, 50 percent  of #3."
+        );
+        assert_eq!(
+            replace_symbols(text, &regex, &symbols, &levels, SymbolLevel::Most),
+            "Done, 50 percent  of  number 3."
+        );
+        assert_eq!(
+            replace_symbols(text, &regex, &symbols, &levels, SymbolLevel::All),
+            "Done comma  50 percent  of  number 3 dot "
+        );
+    }
 }
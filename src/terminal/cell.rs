@@ -3,6 +3,28 @@
 //! Screen readers need to track not just what's displayed, but also maintain
 //! a stable representation for review cursor navigation and reading.
 
+/// A color set with SGR
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Color {
+    /// One of the 256 palette colors (0-15 are the basic and bright colors)
+    Indexed(u8),
+    /// A 24-bit color
+    Rgb(u8, u8, u8),
+}
+
+/// Display attributes of a cell
+///
+/// Only what screen reading needs is kept: menu-driven programs show the
+/// selected item with reverse video or a background color.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Attrs {
+    /// Reverse video (SGR 7)
+    pub reverse: bool,
+
+    /// Background color, None for the terminal default
+    pub background: Option<Color>,
+}
+
 /// A single character cell in the terminal
 ///
 /// Each cell represents one character position that the screen reader can navigate to.
@@ -17,6 +39,9 @@ pub struct Cell {
     /// Whether this cell is part of a wide character (CJK, emoji, etc.)
     /// Important for review cursor navigation - we need to skip continuation cells
     pub is_wide_continuation: bool,
+
+    /// Display attributes the character was drawn with
+    pub attrs: Attrs,
 }
 
 impl Cell {
@@ -25,6 +50,7 @@ impl Cell {
         Self {
             data: ' ',
             is_wide_continuation: false,
+            attrs: Attrs::default(),
         }
     }
 
@@ -33,6 +59,7 @@ impl Cell {
         Self {
             data: c,
             is_wide_continuation: false,
+            attrs: Attrs::default(),
         }
     }
 
@@ -42,6 +69,7 @@ impl Cell {
         Self {
             data: '\0',
             is_wide_continuation: true,
+            attrs: Attrs::default(),
         }
     }
 
@@ -49,6 +77,16 @@ impl Cell {
     pub fn clear(&mut self) {
         self.data = ' ';
         self.is_wide_continuation = false;
+        self.attrs = Attrs::default();
+    }
+
+    /// Erase cell to a blank space in the given background
+    ///
+    /// Terminals erase with the current background color, so programs
+    /// like dialog paint whole areas by setting a color and erasing.
+    pub fn erase(&mut self, background: Option<Color>) {
+        self.clear();
+        self.attrs.background = background;
     }
 }
 
@@ -88,10 +126,30 @@ mod tests {
         let mut cell = Cell {
             data: 'X',
             is_wide_continuation: true,
+            attrs: Attrs {
+                reverse: true,
+                background: Some(Color::Indexed(4)),
+            },
         };
         cell.clear();
         assert_eq!(cell.data, ' ');
         assert!(!cell.is_wide_continuation);
+        assert_eq!(cell.attrs, Attrs::default());
+    }
+
+    #[test]
+    fn test_erase_keeps_background() {
+        let mut cell = Cell::with_char('X');
+        cell.attrs.reverse = true;
+        cell.erase(Some(Color::Indexed(4)));
+        assert_eq!(cell.data, ' ');
+        assert_eq!(
+            cell.attrs,
+            Attrs {
+                reverse: false,
+                background: Some(Color::Indexed(4)),
+            }
+        );
     }
 
     #[test]
@@ -2,6 +2,7 @@
 //!
 //! Separated from Emulator to avoid borrow checker issues
 
+use super::cell::{Attrs, Color};
 use super::{Cell, Screen};
 use crate::speech::SpeechBuffer;
 use log::trace;
@@ -39,6 +40,32 @@ impl<'a> ScreenPerformer<'a> {
     }
 }
 
+/// Parse an extended SGR color (38 or 48)
+///
+/// The color is given either as colon subparameters (`48:5:n`) or as the
+/// following parameters (`48;5;n`, `48;2;r;g;b`), which are consumed.
+fn extended_color<'p>(param: &[u16], rest: &mut impl Iterator<Item = &'p [u16]>) -> Option<Color> {
+    let mut next = |i: usize| -> u16 {
+        if param.len() > 1 {
+            param.get(i).copied().unwrap_or(0)
+        } else {
+            rest.next().and_then(|p| p.first().copied()).unwrap_or(0)
+        }
+    };
+    match next(1) {
+        5 => Some(Color::Indexed(next(2) as u8)),
+        2 => {
+            // Colon form may include a color space id before the components
+            let skip = if param.len() > 5 { 1 } else { 0 };
+            let r = next(2 + skip) as u8;
+            let g = next(3 + skip) as u8;
+            let b = next(4 + skip) as u8;
+            Some(Color::Rgb(r, g, b))
+        }
+        _ => None,
+    }
+}
+
 impl<'a> Perform for ScreenPerformer<'a> {
     /// Print a character to the screen
     ///
@@ -87,6 +114,7 @@ impl<'a> Perform for ScreenPerformer<'a> {
             if let Some(cell) = row.get_mut(x as usize) {
                 cell.data = c;
                 cell.is_wide_continuation = false;
+                cell.attrs = self.screen.attrs;
             }
 
             // For wide characters, mark the next cell as a continuation
@@ -94,6 +122,7 @@ impl<'a> Perform for ScreenPerformer<'a> {
             if width > 1 && (x + 1) < cols {
                 if let Some(next_cell) = row.get_mut((x + 1) as usize) {
                     *next_cell = Cell::wide_continuation();
+                    next_cell.attrs = self.screen.attrs;
                 }
             }
         }
@@ -165,10 +194,33 @@ impl<'a> Perform for ScreenPerformer<'a> {
     fn csi_dispatch(
         &mut self,
         params: &vte::Params,
-        _intermediates: &[u8],
+        intermediates: &[u8],
         _ignore: bool,
         action: char,
     ) {
+        // DEC private modes: CSI ? n h (set) and CSI ? n l (reset)
+        // Other private sequences (e.g. DECSED/DECSEL, CSI ? J / CSI ? K)
+        // fall through and are handled like their plain forms
+        if intermediates == b"?" && matches!(action, 'h' | 'l') {
+            let set = action == 'h';
+            for param in params.iter() {
+                match param.first() {
+                    Some(&1) => self.screen.app_cursor_keys = set,
+                    // Alternate screen, used by full-screen programs
+                    Some(&47) | Some(&1047) | Some(&1049) => {
+                        if set && !self.screen.in_alternate_screen() {
+                            self.screen.save_screen();
+                            self.screen.clear();
+                        } else if !set && self.screen.in_alternate_screen() {
+                            self.screen.restore_screen();
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            return;
+        }
+
         match action {
             // Cursor movement commands
             'H' | 'f' => {
@@ -238,7 +290,12 @@ impl<'a> Perform for ScreenPerformer<'a> {
                 match mode {
                     0 => self.screen.clear_to_end(),   // Clear to end of screen
                     1 => self.screen.clear_to_start(), // Clear to start of screen
-                    2 | 3 => self.screen.clear(),      // Clear entire screen
+                    2 => self.screen.clear(),          // Clear entire screen
+                    3 => {
+                        // Clear screen and scrollback (sent by `clear`)
+                        self.screen.clear();
+                        self.screen.clear_scrollback();
+                    }
                     _ => {}
                 }
             }
@@ -250,31 +307,49 @@ impl<'a> Perform for ScreenPerformer<'a> {
                     .and_then(|p| p.first().copied())
                     .unwrap_or(0);
                 let (x, y) = self.screen.cursor;
+                let background = self.screen.attrs.background;
 
                 if let Some(row) = self.screen.buffer.get_mut(y as usize) {
                     match mode {
                         0 => {
                             // Clear to end of line
                             for cell in row.iter_mut().skip(x as usize) {
-                                cell.clear();
+                                cell.erase(background);
                             }
                         }
                         1 => {
                             // Clear to start of line
                             for cell in row.iter_mut().take(x as usize + 1) {
-                                cell.clear();
+                                cell.erase(background);
                             }
                         }
                         2 => {
                             // Clear entire line
                             for cell in row {
-                                cell.clear();
+                                cell.erase(background);
                             }
                         }
                         _ => {}
                     }
                 }
             }
+            'X' => {
+                // Erase characters (ECH) - blank n cells from the cursor, which stays put
+                let n = params
+                    .iter()
+                    .next()
+                    .and_then(|p| p.first().copied())
+                    .unwrap_or(1)
+                    .max(1);
+                let (x, y) = self.screen.cursor;
+                let background = self.screen.attrs.background;
+
+                if let Some(row) = self.screen.buffer.get_mut(y as usize) {
+                    for cell in row.iter_mut().skip(x as usize).take(n as usize) {
+                        cell.erase(background);
+                    }
+                }
+            }
 
             // Scrolling - important for screen reader to track content movement
             'S' => {
@@ -362,6 +437,34 @@ impl<'a> Perform for ScreenPerformer<'a> {
                 self.screen.cursor.1 = row.min(self.screen.size.1 - 1);
             }
 
+            // Select Graphic Rendition (SGR) - CSI n m
+            // Only reverse video and background color are kept
+            'm' => {
+                if params.is_empty() {
+                    self.screen.attrs = Attrs::default();
+                }
+                let mut params = params.iter();
+                while let Some(param) = params.next() {
+                    let attrs = &mut self.screen.attrs;
+                    match param.first().copied().unwrap_or(0) {
+                        0 => *attrs = Attrs::default(),
+                        7 => attrs.reverse = true,
+                        27 => attrs.reverse = false,
+                        n @ 40..=47 => attrs.background = Some(Color::Indexed((n - 40) as u8)),
+                        n @ 100..=107 => {
+                            attrs.background = Some(Color::Indexed((n - 100 + 8) as u8))
+                        }
+                        49 => attrs.background = None,
+                        48 => attrs.background = extended_color(param, &mut params),
+                        // Foreground colors are skipped, with their arguments
+                        38 => {
+                            extended_color(param, &mut params);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+
             // Cursor Character Absolute (CHA) - CSI n G
             'G' => {
                 let col = params
@@ -383,7 +486,46 @@ impl<'a> Perform for ScreenPerformer<'a> {
     }
     fn put(&mut self, _byte: u8) {}
     fn unhook(&mut self) {}
-    fn osc_dispatch(&mut self, _params: &[&[u8]], _bell_terminated: bool) {}
+
+    /// Handle OSC sequences
+    ///
+    /// Records what the screen reader can report about the window:
+    /// - OSC 0 / OSC 2: window title
+    /// - OSC 7: working directory, as a file:// URL
+    fn osc_dispatch(&mut self, params: &[&[u8]], _bell_terminated: bool) {
+        let (command, rest) = match params.split_first() {
+            Some(split) => split,
+            None => return,
+        };
+        let value = rest
+            .iter()
+            .map(|p| String::from_utf8_lossy(p))
+            .collect::<Vec<_>>()
+            .join(";");
+
+        match *command {
+            b"0" | b"2" => {
+                trace!("Window title: {}", value);
+                self.screen.title = Some(value);
+            }
+            b"7" => {
+                if let Some(path) = parse_cwd_url(&value) {
+                    trace!("Working directory: {}", path);
+                    self.screen.cwd = Some(path);
+                }
+            }
+            // Shell integration marks: B where input starts after the prompt,
+            // C before command output, D after it, or A at the next prompt
+            // for shells that don't send D
+            b"133" => match rest.first().and_then(|p| p.first()) {
+                Some(b'B') => self.screen.mark_input_start(),
+                Some(b'C') => self.screen.mark_output_start(),
+                Some(b'D') | Some(b'A') => self.screen.mark_output_end(),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
 
     /// Handle ESC sequences
     ///
@@ -457,6 +599,35 @@ impl<'a> Perform for ScreenPerformer<'a> {
     }
 }
 
+/// Extract the path from an OSC 7 `file://host/path` URL
+///
+/// Percent escapes in the path are decoded.
+fn parse_cwd_url(url: &str) -> Option<String> {
+    let rest = url.strip_prefix("file://")?;
+    let path = &rest[rest.find('/')?..];
+
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes
+            .get(i + 1..i + 3)
+            .filter(|_| bytes[i] == b'%')
+            .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    Some(String::from_utf8_lossy(&decoded).into_owned())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -915,4 +1086,167 @@ mod tests {
         // Should be at start of next line
         assert_eq!(screen.cursor, (0, 3));
     }
+
+    #[test]
+    fn test_app_cursor_keys_mode() {
+        let (mut screen, mut speech_buffer, mut last_drawn) = create_test_performer(10, 5);
+        {
+            let mut performer = ScreenPerformer {
+                screen: &mut screen,
+                speech_buffer: &mut speech_buffer,
+                last_drawn: &mut last_drawn,
+                line_pause: false,
+            };
+            let mut parser = vte::Parser::new();
+            for byte in b"\x1b[?1h" {
+                parser.advance(&mut performer, *byte);
+            }
+        }
+        assert!(screen.app_cursor_keys);
+
+        {
+            let mut performer = ScreenPerformer {
+                screen: &mut screen,
+                speech_buffer: &mut speech_buffer,
+                last_drawn: &mut last_drawn,
+                line_pause: false,
+            };
+            let mut parser = vte::Parser::new();
+            for byte in b"\x1b[?25;1l" {
+                parser.advance(&mut performer, *byte);
+            }
+        }
+        assert!(!screen.app_cursor_keys);
+    }
+
+    /// Feed bytes through a parser into a performer on `screen`
+    fn feed(screen: &mut Screen, bytes: &[u8]) {
+        let mut speech_buffer = SpeechBuffer::new();
+        let mut last_drawn = (0, 0);
+        let mut performer = ScreenPerformer {
+            screen,
+            speech_buffer: &mut speech_buffer,
+            last_drawn: &mut last_drawn,
+            line_pause: false,
+        };
+        let mut parser = vte::Parser::new();
+        for byte in bytes {
+            parser.advance(&mut performer, *byte);
+        }
+    }
+
+    #[test]
+    fn test_sgr_attributes() {
+        let mut screen = Screen::new(10, 2);
+        feed(
+            &mut screen,
+            b"a\x1b[7mb\x1b[27;44mc\x1b[48;5;200md\x1b[48:2::1:2:3me\x1b[38;2;9;9;9mf\x1b[mg",
+        );
+
+        let attrs: Vec<Attrs> = screen.buffer[0][..7].iter().map(|c| c.attrs).collect();
+        assert_eq!(attrs[0], Attrs::default());
+        assert!(attrs[1].reverse);
+        assert_eq!(
+            attrs[2],
+            Attrs {
+                reverse: false,
+                background: Some(Color::Indexed(4)),
+            }
+        );
+        assert_eq!(attrs[3].background, Some(Color::Indexed(200)));
+        assert_eq!(attrs[4].background, Some(Color::Rgb(1, 2, 3)));
+        // Foreground color arguments aren't taken as attributes
+        assert_eq!(attrs[5].background, Some(Color::Rgb(1, 2, 3)));
+        assert_eq!(attrs[6], Attrs::default());
+    }
+
+    #[test]
+    fn test_erase_keeps_background() {
+        let blue = Some(Color::Indexed(4));
+        let mut screen = Screen::new(10, 2);
+        feed(&mut screen, b"\x1b[44m\x1b[2J");
+        assert!(screen
+            .buffer
+            .iter()
+            .flatten()
+            .all(|c| c.attrs.background == blue));
+
+        feed(
+            &mut screen,
+            b"\x1b[m\x1b[1;1Habcdef\x1b[1;2H\x1b[41m\x1b[3X",
+        );
+        assert_eq!(screen.get_line_trimmed(0), "a   ef");
+        assert_eq!(screen.cursor, (1, 0));
+        let backgrounds: Vec<_> = screen.buffer[0][..6]
+            .iter()
+            .map(|c| c.attrs.background)
+            .collect();
+        let red = Some(Color::Indexed(1));
+        assert_eq!(backgrounds, vec![None, red, red, red, None, None]);
+
+        feed(&mut screen, b"\x1b[2;5H\x1b[44m\x1b[K");
+        assert!(screen.buffer[1][4..]
+            .iter()
+            .all(|c| c.attrs.background == blue));
+    }
+
+    #[test]
+    fn test_private_erase() {
+        // DECSED and DECSEL erase like ED and EL
+        let mut screen = Screen::new(10, 2);
+        feed(&mut screen, b"abcdef\r\nghijkl\x1b[1;4H\x1b[?K");
+        assert_eq!(screen.get_line_trimmed(0), "abc");
+        assert_eq!(screen.get_line_trimmed(1), "ghijkl");
+        feed(&mut screen, b"\x1b[?J");
+        assert_eq!(screen.get_line_trimmed(1), "");
+    }
+
+    #[test]
+    fn test_alternate_screen() {
+        let mut screen = Screen::new(10, 2);
+        feed(&mut screen, b"shell");
+        feed(&mut screen, b"\x1b[?1049h");
+        assert!(screen.in_alternate_screen());
+        assert_eq!(screen.get_line_trimmed(0), "");
+
+        feed(&mut screen, b"\x1b[Hvim");
+        feed(&mut screen, b"\x1b[?1049l");
+        assert!(!screen.in_alternate_screen());
+        assert_eq!(screen.get_line_trimmed(0), "shell");
+        assert_eq!(screen.cursor, (5, 0));
+    }
+
+    #[test]
+    fn test_osc_output_marks() {
+        let mut screen = Screen::new(10, 5);
+        feed(
+            &mut screen,
+            b"$ ls\r\n\x1b]133;C\x07a\r\nb\r\n\x1b]133;D;0\x07\x1b]133;A\x07$ ",
+        );
+        assert_eq!(screen.last_output_range(), Some(1..3));
+    }
+
+    #[test]
+    fn test_osc_title_and_cwd() {
+        let (mut screen, mut speech_buffer, mut last_drawn) = create_test_performer(10, 5);
+        {
+            let mut performer = ScreenPerformer {
+                screen: &mut screen,
+                speech_buffer: &mut speech_buffer,
+                last_drawn: &mut last_drawn,
+                line_pause: false,
+            };
+            performer.osc_dispatch(&[b"2", b"vim", b" notes.txt"], true);
+            performer.osc_dispatch(&[b"7", b"file://host/home/me/My%20Docs"], true);
+            performer.osc_dispatch(&[b"7", b"not a url"], true);
+        }
+
+        assert_eq!(screen.title.as_deref(), Some("vim; notes.txt"));
+        assert_eq!(screen.last_output_range(), None);
+        assert_eq!(screen.cwd.as_deref(), Some("/home/me/My Docs"));
+        assert_eq!(
+            parse_cwd_url("file:///tmp/100%"),
+            Some("/tmp/100%".to_string())
+        );
+    }
 }
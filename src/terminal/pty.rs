@@ -6,6 +6,7 @@
 
 use crate::{Result, TdsrError};
 use log::{debug, info};
+use nix::sys::termios::{tcgetattr, LocalFlags};
 use nix::unistd::dup;
 use portable_pty::{native_pty_system, Child, CommandBuilder, PtySize};
 use std::io::{Read, Write};
@@ -24,11 +25,11 @@ pub struct Pty {
     writer: Box<dyn Write + Send>,
 
     /// The child process (shell) running in the PTY
-    _child: Box<dyn Child + Send>,
+    child: Box<dyn Child + Send>,
 
     /// Duplicated file descriptor for the PTY (for event loop registration)
     /// This is our own copy that stays valid even after master is consumed
-    _fd_owner: OwnedFd,
+    fd_owner: OwnedFd,
 
     /// Raw file descriptor for select/mio
     fd: RawFd,
@@ -117,8 +118,8 @@ impl Pty {
         Ok(Self {
             reader,
             writer,
-            _child: child,
-            _fd_owner: fd_owner,
+            child,
+            fd_owner,
             fd,
             _size: size,
         })
@@ -131,6 +132,11 @@ impl Pty {
         self.fd
     }
 
+    /// Process ID of the shell running in the PTY
+    pub fn child_pid(&self) -> Option<u32> {
+        self.child.process_id()
+    }
+
     /// Read output from the PTY
     ///
     /// This is the terminal output that the screen reader will parse and speak
@@ -150,6 +156,25 @@ impl Pty {
         self.writer.flush().map_err(TdsrError::Io)
     }
 
+    /// Check whether the program in the PTY is hiding typed input
+    ///
+    /// Password prompts turn terminal echo off. Line editors like readline
+    /// also turn echo off but do their own echoing in raw mode, so only
+    /// canonical (line-buffered) input with echo off counts as hidden.
+    /// The master shares the slave's settings, so they can be read here.
+    pub fn input_hidden(&self) -> bool {
+        match tcgetattr(&self.fd_owner) {
+            Ok(termios) => {
+                let flags = termios.local_flags;
+                flags.contains(LocalFlags::ICANON) && !flags.contains(LocalFlags::ECHO)
+            }
+            Err(e) => {
+                debug!("Failed to read PTY terminal settings: {}", e);
+                false
+            }
+        }
+    }
+
     /// Resize the terminal
     ///
     /// Called when user resizes their terminal window (SIGWINCH).
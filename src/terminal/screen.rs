@@ -4,7 +4,13 @@
 //! It maintains a 2D grid of cells that represents what's currently visible
 //! in the terminal, allowing the review cursor to read any position.
 
+use super::cell::Attrs;
 use super::Cell;
+use std::collections::VecDeque;
+use std::ops::Range;
+
+/// Default number of lines kept in the scrollback
+pub const DEFAULT_SCROLLBACK_LINES: usize = 1000;
 
 /// Terminal screen buffer that holds the visual state for screen reader access
 ///
@@ -35,6 +41,40 @@ pub struct Screen {
     /// Allows screen reader to restore previous content when apps exit
     saved_buffer: Option<Vec<Vec<Cell>>>,
 
+    /// Lines that have scrolled off the top of the screen, oldest first
+    /// Kept as text so search and reading can reach past the visible screen
+    scrollback: VecDeque<String>,
+
+    /// Maximum number of scrollback lines kept
+    scrollback_limit: usize,
+
+    /// Lines pushed to the scrollback so far, including ones since dropped
+    /// Gives each line a fixed number for shell integration marks
+    lines_scrolled: u64,
+
+    /// Line where the running command's output began (OSC 133 ; C)
+    output_start: Option<u64>,
+
+    /// Lines of the last finished command's output, end exclusive (OSC 133 ; D)
+    last_output: Option<(u64, u64)>,
+
+    /// Line and column where input starts after the prompt, from OSC 133 ; B
+    /// or where the user started typing
+    input_start: Option<(u64, u16)>,
+
+    /// Window title set by the program (OSC 0 or 2)
+    pub title: Option<String>,
+
+    /// Working directory reported by the shell (OSC 7)
+    pub cwd: Option<String>,
+
+    /// Attributes applied to newly printed characters, set by SGR
+    pub attrs: Attrs,
+
+    /// Application cursor keys mode (DECCKM), set by CSI ? 1 h
+    /// Arrow keys sent to the program must use ESC O rather than ESC [
+    pub app_cursor_keys: bool,
+
     /// Accumulated scroll count since last check
     /// Positive = scrolled up (content moved up, so review cursor should move up to follow)
     /// Used by screen reader to adjust review cursor after processing PTY output
@@ -53,6 +93,16 @@ impl Screen {
             scroll_region: None,
             saved_cursor: None,
             saved_buffer: None,
+            scrollback: VecDeque::new(),
+            scrollback_limit: DEFAULT_SCROLLBACK_LINES,
+            lines_scrolled: 0,
+            output_start: None,
+            last_output: None,
+            input_start: None,
+            title: None,
+            cwd: None,
+            attrs: Attrs::default(),
+            app_cursor_keys: false,
             scroll_offset: 0,
         }
     }
@@ -88,6 +138,117 @@ impl Screen {
         self.get_line(y).trim_end().to_string()
     }
 
+    /// Number of lines in the scrollback
+    pub fn scrollback_len(&self) -> usize {
+        self.scrollback.len()
+    }
+
+    /// Get a scrollback line (0 is the oldest)
+    pub fn scrollback_line(&self, index: usize) -> Option<&str> {
+        self.scrollback.get(index).map(String::as_str)
+    }
+
+    /// Set how many scrollback lines are kept, dropping the oldest if needed
+    pub fn set_scrollback_limit(&mut self, limit: usize) {
+        self.scrollback_limit = limit;
+        while self.scrollback.len() > limit {
+            self.scrollback.pop_front();
+        }
+    }
+
+    /// Discard the scrollback
+    pub fn clear_scrollback(&mut self) {
+        self.scrollback.clear();
+    }
+
+    /// All scrollback and screen lines, trimmed, oldest first
+    ///
+    /// Screen row y is at index `scrollback_len() + y`.
+    pub fn all_lines(&self) -> Vec<String> {
+        self.scrollback
+            .iter()
+            .cloned()
+            .chain((0..self.size.1).map(|y| self.get_line_trimmed(y)))
+            .collect()
+    }
+
+    /// Fixed number of the oldest scrollback line, counting lines dropped
+    ///
+    /// Index `i` into `all_lines` is line number `first_line_number() + i`;
+    /// unlike the index, the number stays with its line as the oldest
+    /// scrollback lines are dropped.
+    pub fn first_line_number(&self) -> u64 {
+        self.lines_scrolled - self.scrollback.len() as u64
+    }
+
+    /// Fixed number of a screen row, counting lines scrolled away
+    fn line_number(&self, y: u16) -> u64 {
+        self.lines_scrolled + y as u64
+    }
+
+    /// Note that command output starts at the cursor line (OSC 133 ; C)
+    pub fn mark_output_start(&mut self) {
+        if !self.in_alternate_screen() {
+            self.output_start = Some(self.line_number(self.cursor.1));
+        }
+    }
+
+    /// Note that command output has finished before the cursor (OSC 133 ; D)
+    pub fn mark_output_end(&mut self) {
+        if let Some(start) = self.output_start.take() {
+            let (x, y) = self.cursor;
+            let end = self.line_number(y) + if x > 0 { 1 } else { 0 };
+            self.last_output = Some((start, end.max(start)));
+        }
+    }
+
+    /// Note that the prompt ends and input starts at the cursor (OSC 133 ; B)
+    pub fn mark_input_start(&mut self) {
+        self.input_start = Some((self.line_number(self.cursor.1), self.cursor.0));
+    }
+
+    /// Note that the user is typing a key
+    ///
+    /// The first key typed on a line marks where input starts, for shells
+    /// that don't send OSC 133 ; B.
+    pub fn note_typing(&mut self) {
+        if self.input_column().is_none() {
+            self.mark_input_start();
+        }
+    }
+
+    /// Column where input starts on the cursor line, if known
+    pub fn input_column(&self) -> Option<u16> {
+        match self.input_start {
+            Some((line, x)) if line == self.line_number(self.cursor.1) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// Lines of the last command's output as indexes into `all_lines`
+    ///
+    /// Uses shell integration marks, covering a command still running
+    /// up to the cursor. Output partly dropped from the scrollback is cut
+    /// short. Returns None without marks, or if it has all been dropped.
+    pub fn last_output_range(&self) -> Option<Range<usize>> {
+        let (start, end) = match self.output_start {
+            Some(start) => (start, self.line_number(self.cursor.1) + 1),
+            None => self.last_output?,
+        };
+        let first = self.first_line_number();
+        if end <= first {
+            return None;
+        }
+        let start = (start.max(first) - first) as usize;
+        let end = (end - first) as usize;
+        let end = end.min(self.scrollback.len() + self.size.1 as usize);
+        if start < end {
+            Some(start..end)
+        } else {
+            None
+        }
+    }
+
     /// Resize the screen buffer
     /// Called when terminal window size changes (SIGWINCH)
     pub fn resize(&mut self, cols: u16, rows: u16) {
@@ -111,10 +272,13 @@ impl Screen {
 
     /// Clear the entire screen
     /// Used by terminal clear commands
+    ///
+    /// Erasing keeps the current background color, as terminals do.
     pub fn clear(&mut self) {
+        let background = self.attrs.background;
         for row in &mut self.buffer {
             for cell in row {
-                cell.clear();
+                cell.erase(background);
             }
         }
     }
@@ -122,18 +286,19 @@ impl Screen {
     /// Clear from cursor to end of screen
     pub fn clear_to_end(&mut self) {
         let (x, y) = self.cursor;
+        let background = self.attrs.background;
 
         // Clear rest of current line
         if let Some(row) = self.buffer.get_mut(y as usize) {
             for cell in row.iter_mut().skip(x as usize) {
-                cell.clear();
+                cell.erase(background);
             }
         }
 
         // Clear all lines below
         for row in self.buffer.iter_mut().skip(y as usize + 1) {
             for cell in row {
-                cell.clear();
+                cell.erase(background);
             }
         }
     }
@@ -141,18 +306,19 @@ impl Screen {
     /// Clear from start of screen to cursor
     pub fn clear_to_start(&mut self) {
         let (x, y) = self.cursor;
+        let background = self.attrs.background;
 
         // Clear all lines above
         for row in self.buffer.iter_mut().take(y as usize) {
             for cell in row {
-                cell.clear();
+                cell.erase(background);
             }
         }
 
         // Clear start of current line to cursor
         if let Some(row) = self.buffer.get_mut(y as usize) {
             for cell in row.iter_mut().take(x as usize + 1) {
-                cell.clear();
+                cell.erase(background);
             }
         }
     }
@@ -172,7 +338,20 @@ impl Screen {
             return;
         }
 
+        // Lines leaving the top of the whole screen go to the scrollback,
+        // but not from alternate screen apps like vim
+        let keep_scrollback = top == 0 && self.saved_buffer.is_none() && self.scrollback_limit > 0;
+
         for _ in 0..lines {
+            if keep_scrollback {
+                let line = self.get_line_trimmed(0);
+                self.scrollback.push_back(line);
+                self.lines_scrolled += 1;
+                if self.scrollback.len() > self.scrollback_limit {
+                    self.scrollback.pop_front();
+                }
+            }
+
             // Shift each line in the scroll region up by one
             // This discards the top line and leaves space at bottom
             for y in top..bottom {
@@ -350,11 +529,19 @@ impl Screen {
     pub fn restore_screen(&mut self) {
         if let Some(buffer) = self.saved_buffer.take() {
             self.buffer = buffer;
+            // The window may have been resized while the app ran
+            let (cols, rows) = self.size;
+            self.resize(cols, rows);
         }
         if let Some(cursor) = self.saved_cursor.take() {
             self.cursor = cursor;
         }
     }
+
+    /// Check if a full-screen program has switched to the alternate screen
+    pub fn in_alternate_screen(&self) -> bool {
+        self.saved_buffer.is_some()
+    }
 }
 
 #[cfg(test)]
@@ -698,4 +885,76 @@ mod tests {
         assert_eq!(screen.get_line_trimmed(3), ""); // New blank line at bottom of region
         assert_eq!(screen.get_char(0, 4), Some('E')); // Outside region, unchanged
     }
+
+    #[test]
+    fn test_scrollback() {
+        let mut screen = Screen::new(5, 2);
+        screen.buffer[0][0].data = 'a';
+        screen.buffer[1][0].data = 'b';
+
+        screen.scroll_up(1);
+        assert_eq!(screen.scrollback_len(), 1);
+        assert_eq!(screen.scrollback_line(0), Some("a"));
+        assert_eq!(screen.all_lines(), vec!["a", "b", ""]);
+
+        // Limited, dropping the oldest lines
+        screen.set_scrollback_limit(2);
+        screen.scroll_up(2);
+        assert_eq!(screen.scrollback_len(), 2);
+        assert_eq!(screen.scrollback_line(0), Some("b"));
+
+        // Scrolling inside a region doesn't add to the scrollback
+        screen.scroll_region = Some((1, 1));
+        screen.scroll_up(1);
+        assert_eq!(screen.scrollback_len(), 2);
+    }
+
+    #[test]
+    fn test_output_marks() {
+        let mut screen = Screen::new(5, 3);
+        assert_eq!(screen.last_output_range(), None);
+
+        // Command typed on row 0, output on rows 1-3, scrolling one line away
+        screen.cursor = (0, 1);
+        screen.mark_output_start();
+        assert_eq!(screen.last_output_range(), Some(1..2));
+        screen.scroll_up(1);
+        screen.cursor = (0, 2);
+        screen.mark_output_end();
+        assert_eq!(screen.last_output_range(), Some(1..3));
+
+        // Marks follow the lines as they scroll further
+        screen.scroll_up(1);
+        assert_eq!(screen.last_output_range(), Some(1..3));
+
+        // Output dropped from the scrollback is gone
+        screen.set_scrollback_limit(1);
+        assert_eq!(screen.last_output_range(), Some(0..2));
+        screen.clear_scrollback();
+        assert_eq!(screen.last_output_range(), Some(0..1));
+    }
+
+    #[test]
+    fn test_input_column() {
+        let mut screen = Screen::new(20, 3);
+        assert_eq!(screen.input_column(), None);
+
+        // The first key typed after the prompt marks the start of input
+        screen.cursor = (2, 0);
+        screen.note_typing();
+        screen.cursor = (6, 0);
+        screen.note_typing();
+        assert_eq!(screen.input_column(), Some(2));
+
+        // Only for the line it was typed on
+        screen.cursor = (2, 1);
+        assert_eq!(screen.input_column(), None);
+
+        // Shell integration marks take precedence
+        screen.cursor = (4, 1);
+        screen.mark_input_start();
+        screen.cursor = (9, 1);
+        screen.note_typing();
+        assert_eq!(screen.input_column(), Some(4));
+    }
 }
@@ -0,0 +1,346 @@
+//! Built-in names for non-ASCII characters
+//!
+//! Modern command line tools decorate their output with arrows, check
+//! marks, box drawing and emoji. Speech engines either skip these or read
+//! them poorly, so this table gives them short spoken names. Emoji use the
+//! Unicode CLDR short names. Entries in the user's `[symbols]` section
+//! always take priority over this table.
+
+use crate::symbols::SymbolLevel;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+
+/// Built-in character names with the symbol level they are spoken at in text
+///
+/// Emoji and status marks carry meaning ("✅", "❌"), so they are spoken
+/// at every level. Decorative characters like box drawing are only spoken
+/// at higher levels so that tables and borders don't drown out content.
+const NAMES: &[(char, &str, SymbolLevel)] = &[
+    // Typography
+    ('\u{00a0}', "no break space", SymbolLevel::Character),
+    ('\u{00a7}', "section", SymbolLevel::Some),
+    ('\u{00a9}', "copyright", SymbolLevel::Some),
+    ('\u{00ab}', "left double angle quote", SymbolLevel::Most),
+    ('\u{00ae}', "registered", SymbolLevel::Some),
+    ('\u{00b0}', "degrees", SymbolLevel::Some),
+    ('\u{00b6}', "pilcrow", SymbolLevel::Some),
+    ('\u{00b7}', "middle dot", SymbolLevel::Most),
+    ('\u{00bb}', "right double angle quote", SymbolLevel::Most),
+    ('\u{2013}', "en dash", SymbolLevel::Most),
+    ('\u{2014}', "em dash", SymbolLevel::Most),
+    ('\u{2018}', "left quote", SymbolLevel::Most),
+    ('\u{2019}', "right quote", SymbolLevel::All),
+    ('\u{201c}', "left double quote", SymbolLevel::Most),
+    ('\u{201d}', "right double quote", SymbolLevel::Most),
+    ('\u{2020}', "dagger", SymbolLevel::Some),
+    ('\u{2022}', "bullet", SymbolLevel::Some),
+    ('\u{2026}', "ellipsis", SymbolLevel::All),
+    ('\u{2122}', "trademark", SymbolLevel::Some),
+    // Currency
+    ('\u{00a2}', "cents", SymbolLevel::Some),
+    ('\u{00a3}', "pound", SymbolLevel::Some),
+    ('\u{00a5}', "yen", SymbolLevel::Some),
+    ('\u{20ac}', "euro", SymbolLevel::Some),
+    ('\u{20b9}', "rupee", SymbolLevel::Some),
+    // Math
+    ('\u{00b1}', "plus or minus", SymbolLevel::Some),
+    ('\u{00d7}', "times", SymbolLevel::Some),
+    ('\u{00f7}', "divided by", SymbolLevel::Some),
+    ('\u{2200}', "for all", SymbolLevel::Some),
+    ('\u{2203}', "there exists", SymbolLevel::Some),
+    ('\u{2208}', "element of", SymbolLevel::Some),
+    ('\u{2211}', "sum", SymbolLevel::Some),
+    ('\u{2212}', "minus", SymbolLevel::Some),
+    ('\u{221a}', "square root", SymbolLevel::Some),
+    ('\u{221e}', "infinity", SymbolLevel::Some),
+    ('\u{2227}', "and", SymbolLevel::Some),
+    ('\u{2228}', "or", SymbolLevel::Some),
+    ('\u{2248}', "almost equal to", SymbolLevel::Some),
+    ('\u{2260}', "not equal to", SymbolLevel::Some),
+    ('\u{2261}', "identical to", SymbolLevel::Some),
+    ('\u{2264}', "less than or equal to", SymbolLevel::Some),
+    ('\u{2265}', "greater than or equal to", SymbolLevel::Some),
+    ('\u{03bb}', "lambda", SymbolLevel::Some),
+    ('\u{03bc}', "mu", SymbolLevel::Some),
+    ('\u{03c0}', "pi", SymbolLevel::Some),
+    // Arrows
+    ('\u{2190}', "left arrow", SymbolLevel::Some),
+    ('\u{2191}', "up arrow", SymbolLevel::Some),
+    ('\u{2192}', "right arrow", SymbolLevel::Some),
+    ('\u{2193}', "down arrow", SymbolLevel::Some),
+    ('\u{2194}', "left right arrow", SymbolLevel::Some),
+    ('\u{21b5}', "return arrow", SymbolLevel::Some),
+    ('\u{21d2}', "implies", SymbolLevel::Some),
+    ('\u{21e1}', "dashed up arrow", SymbolLevel::Some),
+    ('\u{21e3}', "dashed down arrow", SymbolLevel::Some),
+    ('\u{25b2}', "up triangle", SymbolLevel::Some),
+    ('\u{25b6}', "right triangle", SymbolLevel::Some),
+    ('\u{25bc}', "down triangle", SymbolLevel::Some),
+    ('\u{25c0}', "left triangle", SymbolLevel::Some),
+    ('\u{276f}', "right angle", SymbolLevel::Some),
+    ('\u{279c}', "right arrow", SymbolLevel::Some),
+    ('\u{27a4}', "right arrowhead", SymbolLevel::Some),
+    ('\u{2b05}', "left arrow", SymbolLevel::None),
+    ('\u{2b06}', "up arrow", SymbolLevel::None),
+    ('\u{2b07}', "down arrow", SymbolLevel::None),
+    ('\u{27a1}', "right arrow", SymbolLevel::None),
+    // Status marks
+    ('\u{25cf}', "black circle", SymbolLevel::Some),
+    ('\u{25cb}', "white circle", SymbolLevel::Some),
+    ('\u{25a0}', "black square", SymbolLevel::Some),
+    ('\u{25a1}', "white square", SymbolLevel::Some),
+    ('\u{2605}', "star", SymbolLevel::Some),
+    ('\u{2606}', "white star", SymbolLevel::Some),
+    ('\u{2610}', "ballot box", SymbolLevel::None),
+    ('\u{2611}', "ballot box with check", SymbolLevel::None),
+    ('\u{2612}', "ballot box with x", SymbolLevel::None),
+    ('\u{2713}', "check", SymbolLevel::None),
+    ('\u{2714}', "check mark", SymbolLevel::None),
+    ('\u{2715}', "cross", SymbolLevel::None),
+    ('\u{2716}', "multiply", SymbolLevel::None),
+    ('\u{2717}', "ballot x", SymbolLevel::None),
+    ('\u{2718}', "heavy ballot x", SymbolLevel::None),
+    // Box drawing
+    ('\u{2500}', "horizontal line", SymbolLevel::Most),
+    ('\u{2501}', "heavy horizontal line", SymbolLevel::Most),
+    ('\u{2502}', "vertical line", SymbolLevel::Most),
+    ('\u{2503}', "heavy vertical line", SymbolLevel::Most),
+    ('\u{250c}', "top left corner", SymbolLevel::Most),
+    ('\u{250f}', "heavy top left corner", SymbolLevel::Most),
+    ('\u{2510}', "top right corner", SymbolLevel::Most),
+    ('\u{2513}', "heavy top right corner", SymbolLevel::Most),
+    ('\u{2514}', "bottom left corner", SymbolLevel::Most),
+    ('\u{2517}', "heavy bottom left corner", SymbolLevel::Most),
+    ('\u{2518}', "bottom right corner", SymbolLevel::Most),
+    ('\u{251b}', "heavy bottom right corner", SymbolLevel::Most),
+    ('\u{251c}', "left tee", SymbolLevel::Most),
+    ('\u{2523}', "heavy left tee", SymbolLevel::Most),
+    ('\u{2524}', "right tee", SymbolLevel::Most),
+    ('\u{252b}', "heavy right tee", SymbolLevel::Most),
+    ('\u{252c}', "top tee", SymbolLevel::Most),
+    ('\u{2533}', "heavy top tee", SymbolLevel::Most),
+    ('\u{2534}', "bottom tee", SymbolLevel::Most),
+    ('\u{253b}', "heavy bottom tee", SymbolLevel::Most),
+    ('\u{253c}', "cross", SymbolLevel::Most),
+    ('\u{254b}', "heavy cross", SymbolLevel::Most),
+    ('\u{2550}', "double horizontal line", SymbolLevel::Most),
+    ('\u{2551}', "double vertical line", SymbolLevel::Most),
+    ('\u{2554}', "double top left corner", SymbolLevel::Most),
+    ('\u{2557}', "double top right corner", SymbolLevel::Most),
+    ('\u{255a}', "double bottom left corner", SymbolLevel::Most),
+    ('\u{255d}', "double bottom right corner", SymbolLevel::Most),
+    ('\u{2560}', "double left tee", SymbolLevel::Most),
+    ('\u{2563}', "double right tee", SymbolLevel::Most),
+    ('\u{2566}', "double top tee", SymbolLevel::Most),
+    ('\u{2569}', "double bottom tee", SymbolLevel::Most),
+    ('\u{256c}', "double cross", SymbolLevel::Most),
+    ('\u{256d}', "rounded top left corner", SymbolLevel::Most),
+    ('\u{256e}', "rounded top right corner", SymbolLevel::Most),
+    ('\u{256f}', "rounded bottom right corner", SymbolLevel::Most),
+    ('\u{2570}', "rounded bottom left corner", SymbolLevel::Most),
+    // Block elements (progress bars)
+    ('\u{2588}', "full block", SymbolLevel::Most),
+    ('\u{2591}', "light shade", SymbolLevel::Most),
+    ('\u{2592}', "medium shade", SymbolLevel::Most),
+    ('\u{2593}', "dark shade", SymbolLevel::Most),
+    ('\u{258c}', "left half block", SymbolLevel::Most),
+    ('\u{2590}', "right half block", SymbolLevel::Most),
+    // Powerline and Nerd Font prompt glyphs (private use area)
+    ('\u{e0a0}', "branch", SymbolLevel::Some),
+    ('\u{e0a1}', "line number", SymbolLevel::Some),
+    ('\u{e0a2}', "lock", SymbolLevel::Some),
+    ('\u{e0b0}', "separator", SymbolLevel::All),
+    ('\u{e0b1}', "separator", SymbolLevel::All),
+    ('\u{e0b2}', "separator", SymbolLevel::All),
+    ('\u{e0b3}', "separator", SymbolLevel::All),
+    // Emoji (CLDR short names)
+    ('\u{2139}', "information", SymbolLevel::None),
+    ('\u{231b}', "hourglass done", SymbolLevel::None),
+    ('\u{23f1}', "stopwatch", SymbolLevel::None),
+    ('\u{23f3}', "hourglass not done", SymbolLevel::None),
+    ('\u{2601}', "cloud", SymbolLevel::None),
+    ('\u{2699}', "gear", SymbolLevel::None),
+    ('\u{26a0}', "warning", SymbolLevel::None),
+    ('\u{26a1}', "high voltage", SymbolLevel::None),
+    ('\u{26d4}', "no entry", SymbolLevel::None),
+    ('\u{2705}', "check mark button", SymbolLevel::None),
+    ('\u{2728}', "sparkles", SymbolLevel::None),
+    ('\u{274c}', "cross mark", SymbolLevel::None),
+    ('\u{2753}', "red question mark", SymbolLevel::None),
+    ('\u{2757}', "red exclamation mark", SymbolLevel::None),
+    ('\u{2764}', "red heart", SymbolLevel::None),
+    ('\u{2795}', "plus", SymbolLevel::None),
+    ('\u{2796}', "minus", SymbolLevel::None),
+    ('\u{2b50}', "star", SymbolLevel::None),
+    ('\u{267b}', "recycling symbol", SymbolLevel::None),
+    ('\u{1f310}', "globe with meridians", SymbolLevel::None),
+    ('\u{1f331}', "seedling", SymbolLevel::None),
+    ('\u{1f389}', "party popper", SymbolLevel::None),
+    ('\u{1f3af}', "direct hit", SymbolLevel::None),
+    ('\u{1f3c1}', "chequered flag", SymbolLevel::None),
+    ('\u{1f40d}', "snake", SymbolLevel::None),
+    ('\u{1f40b}', "whale", SymbolLevel::None),
+    ('\u{1f41b}', "bug", SymbolLevel::None),
+    ('\u{1f433}', "spouting whale", SymbolLevel::None),
+    ('\u{1f440}', "eyes", SymbolLevel::None),
+    ('\u{1f44d}', "thumbs up", SymbolLevel::None),
+    ('\u{1f44e}', "thumbs down", SymbolLevel::None),
+    ('\u{1f480}', "skull", SymbolLevel::None),
+    ('\u{1f4a1}', "light bulb", SymbolLevel::None),
+    ('\u{1f4a5}', "collision", SymbolLevel::None),
+    ('\u{1f4bb}', "laptop", SymbolLevel::None),
+    ('\u{1f4c1}', "file folder", SymbolLevel::None),
+    ('\u{1f4c2}', "open file folder", SymbolLevel::None),
+    ('\u{1f4c4}', "page facing up", SymbolLevel::None),
+    ('\u{1f4c8}', "chart increasing", SymbolLevel::None),
+    ('\u{1f4c9}', "chart decreasing", SymbolLevel::None),
+    ('\u{1f4cc}', "pushpin", SymbolLevel::None),
+    ('\u{1f4da}', "books", SymbolLevel::None),
+    ('\u{1f4dd}', "memo", SymbolLevel::None),
+    ('\u{1f4e6}', "package", SymbolLevel::None),
+    (
+        '\u{1f504}',
+        "counterclockwise arrows button",
+        SymbolLevel::None,
+    ),
+    (
+        '\u{1f50d}',
+        "magnifying glass tilted left",
+        SymbolLevel::None,
+    ),
+    ('\u{1f511}', "key", SymbolLevel::None),
+    ('\u{1f512}', "locked", SymbolLevel::None),
+    ('\u{1f513}', "unlocked", SymbolLevel::None),
+    ('\u{1f514}', "bell", SymbolLevel::None),
+    ('\u{1f517}', "link", SymbolLevel::None),
+    ('\u{1f525}', "fire", SymbolLevel::None),
+    ('\u{1f527}', "wrench", SymbolLevel::None),
+    ('\u{1f528}', "hammer", SymbolLevel::None),
+    ('\u{1f534}', "red circle", SymbolLevel::None),
+    ('\u{1f535}', "blue circle", SymbolLevel::None),
+    ('\u{1f5a5}', "desktop computer", SymbolLevel::None),
+    ('\u{1f5d1}', "wastebasket", SymbolLevel::None),
+    ('\u{1f600}', "grinning face", SymbolLevel::None),
+    ('\u{1f602}', "face with tears of joy", SymbolLevel::None),
+    ('\u{1f642}', "slightly smiling face", SymbolLevel::None),
+    ('\u{1f680}', "rocket", SymbolLevel::None),
+    ('\u{1f6a7}', "construction", SymbolLevel::None),
+    ('\u{1f6ab}', "prohibited", SymbolLevel::None),
+    ('\u{1f6e0}', "hammer and wrench", SymbolLevel::None),
+    ('\u{1f914}', "thinking face", SymbolLevel::None),
+    ('\u{1f980}', "crab", SymbolLevel::None),
+    ('\u{1f916}', "robot", SymbolLevel::None),
+    ('\u{1f9ea}', "test tube", SymbolLevel::None),
+    ('\u{1f9f9}', "broom", SymbolLevel::None),
+    ('\u{1f7e2}', "green circle", SymbolLevel::None),
+    ('\u{1f7e1}', "yellow circle", SymbolLevel::None),
+];
+
+/// Lookup table built from `NAMES`
+static UNICODE_NAMES: Lazy<HashMap<char, (&'static str, SymbolLevel)>> = Lazy::new(|| {
+    NAMES
+        .iter()
+        .map(|&(ch, name, level)| (ch, (name, level)))
+        .collect()
+});
+
+/// Check for emoji presentation selectors and joiners
+///
+/// These are invisible modifiers that follow an emoji. They have no
+/// name of their own and are dropped when speaking named characters.
+pub fn is_modifier(ch: char) -> bool {
+    matches!(ch, '\u{fe0e}' | '\u{fe0f}' | '\u{200d}')
+}
+
+/// Built-in name for a character, if there is one
+pub fn name(ch: char) -> Option<&'static str> {
+    UNICODE_NAMES.get(&ch).map(|(name, _)| *name)
+}
+
+/// Replace named non-ASCII characters in text with their names
+///
+/// Characters in `symbols` are skipped; the user's entries are handled
+/// by `symbols::replace_symbols`. Only names whose level is within
+/// `level` are spoken.
+pub fn replace_names(text: &str, symbols: &HashMap<u32, String>, level: SymbolLevel) -> String {
+    if text.is_ascii() {
+        return text.to_string();
+    }
+
+    let mut result = String::with_capacity(text.len());
+    let mut after_name = false;
+    for ch in text.chars() {
+        if after_name && is_modifier(ch) {
+            continue;
+        }
+        after_name = false;
+
+        match UNICODE_NAMES.get(&ch) {
+            Some((name, name_level))
+                if *name_level <= level && !symbols.contains_key(&(ch as u32)) =>
+            {
+                result.push(' ');
+                result.push_str(name);
+                result.push(' ');
+                after_name = true;
+            }
+            _ => result.push(ch),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_name_lookup() {
+        assert_eq!(name('→'), Some("right arrow"));
+        assert_eq!(name('✅'), Some("check mark button"));
+        assert_eq!(name('🦀'), Some("crab"));
+        assert_eq!(name('a'), None);
+    }
+
+    #[test]
+    fn test_replace_names_by_level() {
+        let symbols = HashMap::new();
+        let text = "✅ build → done ─";
+        assert_eq!(
+            replace_names(text, &symbols, SymbolLevel::None),
+            " check mark button  build → done ─"
+        );
+        assert_eq!(
+            replace_names(text, &symbols, SymbolLevel::Some),
+            " check mark button  build  right arrow  done ─"
+        );
+        assert_eq!(
+            replace_names(text, &symbols, SymbolLevel::Most),
+            " check mark button  build  right arrow  done  horizontal line "
+        );
+    }
+
+    #[test]
+    fn test_drops_emoji_presentation_selector() {
+        let symbols = HashMap::new();
+        assert_eq!(
+            replace_names("⚠\u{fe0f} careful", &symbols, SymbolLevel::None),
+            " warning  careful"
+        );
+    }
+
+    #[test]
+    fn test_user_symbols_take_priority() {
+        let mut symbols = HashMap::new();
+        symbols.insert('→' as u32, "to".to_string());
+        assert_eq!(replace_names("a → b", &symbols, SymbolLevel::All), "a → b");
+    }
+
+    #[test]
+    fn test_no_duplicate_characters() {
+        let mut seen = std::collections::HashSet::new();
+        for (ch, _, _) in NAMES {
+            assert!(seen.insert(*ch), "duplicate name entry for {:?}", ch);
+        }
+    }
+}
@@ -0,0 +1,208 @@
+//! BrlAPI client tests against a fake brltty server
+
+use std::io::{Read, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+use tdsr::braille::brlapi::{key, packet, BrlapiConnection, PROTOCOL_VERSION};
+use tdsr::braille::{BrailleCommand, BrailleDisplay};
+use tdsr::input::KeyAction;
+use tdsr::terminal::Screen;
+use tempfile::TempDir;
+
+/// Display width reported by the fake server
+const WIDTH: u32 = 8;
+
+fn send(stream: &mut UnixStream, ty: u32, payload: &[u8]) {
+    stream
+        .write_all(&(payload.len() as u32).to_be_bytes())
+        .unwrap();
+    stream.write_all(&ty.to_be_bytes()).unwrap();
+    stream.write_all(payload).unwrap();
+}
+
+fn recv(stream: &mut UnixStream) -> (u32, Vec<u8>) {
+    let mut header = [0u8; 8];
+    stream.read_exact(&mut header).unwrap();
+    let size = u32::from_be_bytes(header[0..4].try_into().unwrap()) as usize;
+    let ty = u32::from_be_bytes(header[4..8].try_into().unwrap());
+    let mut payload = vec![0u8; size];
+    stream.read_exact(&mut payload).unwrap();
+    (ty, payload)
+}
+
+fn u32_at(data: &[u8], offset: usize) -> u32 {
+    u32::from_be_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+/// Serve one client: handshake with key auth, report the display size
+/// and accept tty mode, then hand the stream to `session`
+fn spawn_server<F>(session: F) -> (TempDir, PathBuf, PathBuf, thread::JoinHandle<()>)
+where
+    F: FnOnce(&mut UnixStream) + Send + 'static,
+{
+    let dir = TempDir::new().unwrap();
+    let socket = dir.path().join("0");
+    let key_file = dir.path().join("brlapi.key");
+    std::fs::write(&key_file, b"secret").unwrap();
+
+    let listener = UnixListener::bind(&socket).unwrap();
+    let handle = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+
+        send(
+            &mut stream,
+            packet::VERSION,
+            &PROTOCOL_VERSION.to_be_bytes(),
+        );
+        let (ty, payload) = recv(&mut stream);
+        assert_eq!(ty, packet::VERSION);
+        assert_eq!(u32_at(&payload, 0), PROTOCOL_VERSION);
+
+        // Offer key authentication only
+        send(&mut stream, packet::AUTH, &(b'K' as u32).to_be_bytes());
+        let (ty, payload) = recv(&mut stream);
+        assert_eq!(ty, packet::AUTH);
+        assert_eq!(u32_at(&payload, 0), b'K' as u32);
+        assert_eq!(&payload[4..], b"secret");
+        send(&mut stream, packet::ACK, &[]);
+
+        let (ty, _) = recv(&mut stream);
+        assert_eq!(ty, packet::GET_DISPLAY_SIZE);
+        let mut size = WIDTH.to_be_bytes().to_vec();
+        size.extend_from_slice(&1u32.to_be_bytes());
+        send(&mut stream, packet::GET_DISPLAY_SIZE, &size);
+
+        let (ty, payload) = recv(&mut stream);
+        assert_eq!(ty, packet::ENTER_TTY_MODE);
+        assert_eq!(u32_at(&payload, 0), 1);
+        assert_eq!(u32_at(&payload, 4), 7);
+        send(&mut stream, packet::ACK, &[]);
+
+        session(&mut stream);
+    });
+
+    (dir, socket, key_file, handle)
+}
+
+/// Decode the text and cursor fields of a write packet
+fn parse_write(payload: &[u8]) -> (String, u32) {
+    let flags = u32_at(payload, 0);
+    assert_eq!(flags, 0x02 | 0x04 | 0x20 | 0x40);
+    assert_eq!(u32_at(payload, 4), 1);
+    assert_eq!(u32_at(payload, 8), WIDTH);
+    let len = u32_at(payload, 12) as usize;
+    let text = String::from_utf8(payload[16..16 + len].to_vec()).unwrap();
+    let cursor = u32_at(payload, 16 + len);
+    let charset_len = payload[20 + len] as usize;
+    assert_eq!(&payload[21 + len..21 + len + charset_len], b"UTF-8");
+    (text, cursor)
+}
+
+#[test]
+fn test_handshake_and_display_size() {
+    let (_dir, socket, key_file, handle) = spawn_server(|_| {});
+
+    let mut conn = BrlapiConnection::connect(&socket, &key_file).unwrap();
+    assert_eq!(conn.display_size(), (WIDTH, 1));
+    conn.enter_tty_mode(&[7]).unwrap();
+
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_connect_fails_without_server() {
+    let dir = TempDir::new().unwrap();
+    let result = BrlapiConnection::connect(&dir.path().join("0"), &dir.path().join("key"));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_display_shows_review_window() {
+    let (_dir, socket, key_file, handle) = spawn_server(|stream| {
+        let (ty, payload) = recv(stream);
+        assert_eq!(ty, packet::WRITE);
+        assert_eq!(parse_write(&payload), ("abcdefgh".to_string(), 3));
+
+        // Second window of the line, padded to the display width
+        let (ty, payload) = recv(stream);
+        assert_eq!(ty, packet::WRITE);
+        assert_eq!(parse_write(&payload), ("ijkl    ".to_string(), 2));
+
+        let (ty, _) = recv(stream);
+        assert_eq!(ty, packet::LEAVE_TTY_MODE);
+    });
+
+    let mut screen = Screen::new(20, 2);
+    for (x, ch) in "abcdefghijkl".chars().enumerate() {
+        screen.buffer[0][x].data = ch;
+    }
+
+    let mut display = BrailleDisplay::connect(&socket, &key_file, Some(7)).unwrap();
+    assert_eq!(display.width(), WIDTH as u16);
+    display.show(&screen, (2, 0)).unwrap();
+    // Unchanged content is not written again
+    display.show(&screen, (2, 0)).unwrap();
+    display.show(&screen, (9, 0)).unwrap();
+    drop(display);
+
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_display_keys_become_commands() {
+    let (_dir, socket, key_file, handle) = spawn_server(|stream| {
+        for code in [
+            key::TYPE_CMD | key::CMD_LNDN,
+            key::TYPE_CMD | key::CMD_FWINLT,
+            key::TYPE_CMD | (key::BLK_ROUTE << key::CMD_BLK_SHIFT) | 4,
+        ] {
+            let mut payload = ((code >> 32) as u32).to_be_bytes().to_vec();
+            payload.extend_from_slice(&(code as u32).to_be_bytes());
+            send(stream, packet::KEY, &payload);
+        }
+        let (ty, _) = recv(stream);
+        assert_eq!(ty, packet::LEAVE_TTY_MODE);
+    });
+
+    let mut display = BrailleDisplay::connect(&socket, &key_file, Some(7)).unwrap();
+
+    let mut commands = Vec::new();
+    for _ in 0..50 {
+        commands.extend(display.read_commands().unwrap());
+        if commands.len() == 3 {
+            break;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(
+        commands,
+        vec![
+            BrailleCommand::Action(KeyAction::NextLine),
+            BrailleCommand::PanLeft,
+            BrailleCommand::Route(4),
+        ]
+    );
+    drop(display);
+
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_read_fails_when_server_closes() {
+    let (_dir, socket, key_file, handle) = spawn_server(|_| {});
+
+    let mut display = BrailleDisplay::connect(&socket, &key_file, Some(7)).unwrap();
+    handle.join().unwrap();
+
+    let mut result = Ok(Vec::new());
+    for _ in 0..50 {
+        result = display.read_commands();
+        if result.is_err() {
+            break;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    assert!(result.is_err());
+}
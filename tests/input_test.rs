@@ -52,6 +52,18 @@ fn test_keymap_creation() {
     );
     assert_eq!(keymap.get(&b"\x1bo".to_vec()), Some(&KeyAction::NextLine));
 
+    // Test block navigation keys
+    assert_eq!(keymap.get(&b"\x1b{".to_vec()), Some(&KeyAction::PrevBlock));
+    assert_eq!(keymap.get(&b"\x1b}".to_vec()), Some(&KeyAction::NextBlock));
+    assert_eq!(
+        keymap.get(&b"\x1b(".to_vec()),
+        Some(&KeyAction::PrevIndentChange)
+    );
+    assert_eq!(
+        keymap.get(&b"\x1b)".to_vec()),
+        Some(&KeyAction::NextIndentChange)
+    );
+
     // Test word navigation keys
     assert_eq!(keymap.get(&b"\x1bj".to_vec()), Some(&KeyAction::PrevWord));
     assert_eq!(
@@ -68,6 +80,12 @@ fn test_keymap_creation() {
     );
     assert_eq!(keymap.get(&b"\x1b.".to_vec()), Some(&KeyAction::NextChar));
 
+    // Test vertical movement keys
+    assert_eq!(keymap.get(&b"\x1by".to_vec()), Some(&KeyAction::CharUp));
+    assert_eq!(keymap.get(&b"\x1bn".to_vec()), Some(&KeyAction::CharDown));
+    assert_eq!(keymap.get(&b"\x1bY".to_vec()), Some(&KeyAction::WordUp));
+    assert_eq!(keymap.get(&b"\x1bN".to_vec()), Some(&KeyAction::WordDown));
+
     // Test mode keys
     assert_eq!(keymap.get(&b"\x1bc".to_vec()), Some(&KeyAction::Config));
     assert_eq!(keymap.get(&b"\x1bq".to_vec()), Some(&KeyAction::QuietMode));
@@ -77,6 +95,85 @@ fn test_keymap_creation() {
     assert_eq!(keymap.get(&b"\x1b[A".to_vec()), Some(&KeyAction::ArrowUp));
     assert_eq!(keymap.get(&b"\x1b[B".to_vec()), Some(&KeyAction::ArrowDown));
 
+    // Test speech history keys
+    assert_eq!(
+        keymap.get(&b"\x1bh".to_vec()),
+        Some(&KeyAction::HistoryPrev)
+    );
+    assert_eq!(
+        keymap.get(&b"\x1bH".to_vec()),
+        Some(&KeyAction::HistoryNext)
+    );
+    assert_eq!(
+        keymap.get(&b"\x1b;".to_vec()),
+        Some(&KeyAction::HistoryRepeat)
+    );
+
+    // Test symbol level key
+    assert_eq!(
+        keymap.get(&b"\x1bp".to_vec()),
+        Some(&KeyAction::CycleSymbolLevel)
+    );
+
+    // Test where am I key
+    assert_eq!(keymap.get(&b"\x1bw".to_vec()), Some(&KeyAction::WhereAmI));
+
+    // Test cursor routing key
+    assert_eq!(
+        keymap.get(&b"\x1b\\".to_vec()),
+        Some(&KeyAction::RouteCursor)
+    );
+
+    // Test last output key
+    assert_eq!(
+        keymap.get(&b"\x1be".to_vec()),
+        Some(&KeyAction::ReadLastOutput)
+    );
+
+    // Test say all keys
+    assert_eq!(keymap.get(&b"\x1ba".to_vec()), Some(&KeyAction::SayAll));
+    assert_eq!(
+        keymap.get(&b"\x1ba\x1ba".to_vec()),
+        Some(&KeyAction::SayAllFromTop)
+    );
+
+    // Test selection mode toggle
+    assert_eq!(
+        keymap.get(&b"\x1br\x1br".to_vec()),
+        Some(&KeyAction::ToggleSelectionMode)
+    );
+
+    // Test table mode key
+    assert_eq!(keymap.get(&b"\x1bt".to_vec()), Some(&KeyAction::TableMode));
+
+    // Escape on its own reaches the shell, for its Alt bindings
+    assert_eq!(keymap.get(&b"\x1b".to_vec()), None);
+
+    // Test bookmark keys
+    assert_eq!(keymap.get(&b"\x1bb".to_vec()), None);
+    assert_eq!(
+        keymap.get(&b"\x1b'".to_vec()),
+        Some(&KeyAction::SetBookmark)
+    );
+    assert_eq!(
+        keymap.get(&b"\x1bg".to_vec()),
+        Some(&KeyAction::JumpToBookmark)
+    );
+    assert_eq!(
+        keymap.get(&b"\x1bG".to_vec()),
+        Some(&KeyAction::ReadBookmark)
+    );
+
+    // Test search keys
+    assert_eq!(keymap.get(&b"\x1bf".to_vec()), None);
+    assert_eq!(keymap.get(&b"\x1b/".to_vec()), Some(&KeyAction::FindText));
+    assert_eq!(keymap.get(&b"\x1b?".to_vec()), Some(&KeyAction::FindRegex));
+    assert_eq!(keymap.get(&b"\x1bz".to_vec()), Some(&KeyAction::FindNext));
+    assert_eq!(
+        keymap.get(&b"\x1bZ".to_vec()),
+        Some(&KeyAction::FindPrevious)
+    );
+
     // Test double-tap keys
     assert_eq!(
         keymap.get(&b"\x1bk\x1bk".to_vec()),
@@ -86,6 +183,10 @@ fn test_keymap_creation() {
         keymap.get(&b"\x1b,\x1b,".to_vec()),
         Some(&KeyAction::SayCharPhonetic)
     );
+    assert_eq!(
+        keymap.get(&b"\x1bw\x1bw".to_vec()),
+        Some(&KeyAction::WhereAmIDetails)
+    );
 }
 
 #[test]